@@ -0,0 +1,222 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Error, Fields, Generics, Ident, Type};
+
+pub fn encode(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(::fast_collections::Encode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (max_size, body) = match &input.data {
+        Data::Struct(data) => {
+            let (bindings, sizes, encodes) = encode_fields(&data.fields)?;
+            let pattern = pattern(quote!(Self), &data.fields, &bindings);
+            (
+                quote!(0 #(+ #sizes)*),
+                quote! {
+                    let #pattern = self;
+                    #(#encodes)*
+                },
+            )
+        }
+        Data::Enum(data) => {
+            let count = data.variants.len();
+            let mut variant_sizes = Vec::new();
+            let mut arms = Vec::new();
+            for (tag, variant) in data.variants.iter().enumerate() {
+                let tag = tag as u32;
+                let name = &variant.ident;
+                let (bindings, sizes, encodes) = encode_fields(&variant.fields)?;
+                let pattern = pattern(quote!(Self::#name), &variant.fields, &bindings);
+                variant_sizes.push(quote!(0 #(+ #sizes)*));
+                arms.push(quote! {
+                    #pattern => {
+                        <::fast_collections::VarInt as ::fast_collections::EncodeWith<u32>>::encode_with(&#tag, cursor)?;
+                        #(#encodes)*
+                    }
+                });
+            }
+            (
+                quote! {
+                    ::fast_collections::varint_len(#count as i32)
+                        + ::fast_collections::max_encoded_size([#(#variant_sizes),*])
+                },
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                ident,
+                "Encode can not be derived for unions",
+            ))
+        }
+    };
+    Ok(quote! {
+        impl #impl_generics ::fast_collections::Encode for #ident #ty_generics #where_clause {
+            const MAX_ENCODED_SIZE: usize = #max_size;
+
+            fn encode<const __N: usize>(
+                &self,
+                cursor: &mut ::fast_collections::Cursor<u8, __N>,
+            ) -> ::core::result::Result<(), ::fast_collections::EncodeError> {
+                #body
+                Ok(())
+            }
+        }
+    })
+}
+
+pub fn decode(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(::fast_collections::Decode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let value = decode_fields(quote!(Self), &data.fields)?;
+            quote!(Ok(#value))
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for (tag, variant) in data.variants.iter().enumerate() {
+                let tag = tag as u32;
+                let name = &variant.ident;
+                let value = decode_fields(quote!(Self::#name), &variant.fields)?;
+                arms.push(quote!(#tag => Ok(#value),));
+            }
+            quote! {
+                match <::fast_collections::VarInt as ::fast_collections::DecodeWith<u32>>::decode_with(cursor)? {
+                    #(#arms)*
+                    _ => Err(::fast_collections::DecodeError::Malformed),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                ident,
+                "Decode can not be derived for unions",
+            ))
+        }
+    };
+    Ok(quote! {
+        impl #impl_generics ::fast_collections::Decode for #ident #ty_generics #where_clause {
+            fn decode<const __N: usize>(
+                cursor: &mut ::fast_collections::Cursor<u8, __N>,
+            ) -> ::core::result::Result<Self, ::fast_collections::DecodeError> {
+                #body
+            }
+        }
+    })
+}
+
+fn add_bounds(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<_> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+fn bindings(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("__field{}", i),
+        })
+        .collect()
+}
+
+fn pattern(path: TokenStream, fields: &Fields, bindings: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Named(_) => quote!(#path { #(#bindings),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+fn encode_fields(fields: &Fields) -> syn::Result<(Vec<Ident>, Vec<TokenStream>, Vec<TokenStream>)> {
+    let bindings = bindings(fields);
+    let mut sizes = Vec::new();
+    let mut encodes = Vec::new();
+    for (field, binding) in fields.iter().zip(&bindings) {
+        let ty = &field.ty;
+        match strategy(&field.attrs)? {
+            Some(strategy) => {
+                sizes.push(
+                    quote!(<#strategy as ::fast_collections::EncodeWith<#ty>>::MAX_ENCODED_SIZE),
+                );
+                encodes.push(quote!(<#strategy as ::fast_collections::EncodeWith<#ty>>::encode_with(#binding, cursor)?;));
+            }
+            None => {
+                sizes.push(quote!(<#ty as ::fast_collections::Encode>::MAX_ENCODED_SIZE));
+                encodes
+                    .push(quote!(<#ty as ::fast_collections::Encode>::encode(#binding, cursor)?;));
+            }
+        }
+    }
+    Ok((bindings, sizes, encodes))
+}
+
+fn decode_fields(path: TokenStream, fields: &Fields) -> syn::Result<TokenStream> {
+    let mut decodes = Vec::new();
+    for field in fields {
+        let ty = &field.ty;
+        decodes.push(match strategy(&field.attrs)? {
+            Some(strategy) => {
+                quote!(<#strategy as ::fast_collections::DecodeWith<#ty>>::decode_with(cursor)?)
+            }
+            None => quote!(<#ty as ::fast_collections::Decode>::decode(cursor)?),
+        });
+    }
+    let names = fields.iter().map(|field| &field.ident);
+    Ok(match fields {
+        Fields::Named(_) => quote!(#path { #(#names: #decodes),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#decodes),* )),
+        Fields::Unit => quote!(#path),
+    })
+}
+
+///Reads `#[codec(varint | zigzag | be | le | prefix = P | with = S)]` into a strategy type.
+fn strategy(attrs: &[Attribute]) -> syn::Result<Option<TokenStream>> {
+    let mut strategy = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("codec")) {
+        attr.parse_nested_meta(|meta| {
+            let selected = if meta.path.is_ident("varint") {
+                quote!(::fast_collections::VarInt)
+            } else if meta.path.is_ident("zigzag") {
+                quote!(::fast_collections::ZigZag)
+            } else if meta.path.is_ident("be") {
+                quote!(::fast_collections::BigEndian)
+            } else if meta.path.is_ident("le") {
+                quote!(::fast_collections::LittleEndian)
+            } else if meta.path.is_ident("prefix") {
+                let prefix: Type = meta.value()?.parse()?;
+                quote!(::fast_collections::Prefixed<#prefix>)
+            } else if meta.path.is_ident("with") {
+                let with: Type = meta.value()?.parse()?;
+                quote!(#with)
+            } else {
+                return Err(
+                    meta.error("expected `varint`, `zigzag`, `be`, `le`, `prefix` or `with`")
+                );
+            };
+            if strategy.replace(selected).is_some() {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "only one codec attribute is allowed per field",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(strategy)
+}
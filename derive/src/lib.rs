@@ -0,0 +1,94 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error};
+
+mod codec;
+
+///Implements `Pod`, `FromBytes` and `AsBytes`, rejecting structs with padding or non-Pod fields.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    pod(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+///Implements `Encode`, configured per field with
+///`#[codec(varint | zigzag | be | le | prefix = P | with = S)]`.
+#[proc_macro_derive(Encode, attributes(codec))]
+pub fn derive_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    codec::encode(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+///Implements `Decode`, configured per field like [macro@Encode].
+#[proc_macro_derive(Decode, attributes(codec))]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    codec::decode(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn pod(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            ident,
+            "Pod can only be derived for structs",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Pod can not be derived for generic structs",
+        ));
+    }
+    if !has_stable_repr(&input)? {
+        return Err(Error::new_spanned(
+            ident,
+            "Pod requires #[repr(C)], #[repr(transparent)] or #[repr(packed)]",
+        ));
+    }
+    let types: Vec<_> = data.fields.iter().map(|field| &field.ty).collect();
+    let message = format!("`{ident}` must not contain padding to derive Pod");
+    Ok(quote! {
+        const _: () = {
+            fn assert_pod<T: ::fast_collections::Pod>() {}
+            fn assert_fields() {
+                #(assert_pod::<#types>();)*
+            }
+            assert!(
+                ::core::mem::size_of::<#ident>() == 0 #(+ ::core::mem::size_of::<#types>())*,
+                #message
+            );
+        };
+        unsafe impl ::fast_collections::FromBytes for #ident {}
+        unsafe impl ::fast_collections::AsBytes for #ident {}
+        unsafe impl ::fast_collections::Pod for #ident {}
+    })
+}
+
+fn has_stable_repr(input: &DeriveInput) -> syn::Result<bool> {
+    let mut stable = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C")
+                || meta.path.is_ident("transparent")
+                || meta.path.is_ident("packed")
+            {
+                stable = true;
+            }
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(stable)
+}
@@ -0,0 +1,255 @@
+use crate::{Cursor, DecodeError, EncodeError};
+
+///Bit-packed reader and writer over a [Cursor], filling each byte from its least significant bit.
+///The underlying cursor always covers every byte touched, the last one padded with zero bits.
+pub struct BitCursor<const N: usize> {
+    cursor: Cursor<u8, N>,
+    bit_len: usize,
+    bit_pos: usize,
+}
+
+impl<const N: usize> BitCursor<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            cursor: Cursor::new(),
+            bit_len: 0,
+            bit_pos: 0,
+        }
+    }
+
+    ///Continues after the filled bytes of `cursor`, reading from its `pos`.
+    pub fn from_cursor(cursor: Cursor<u8, N>) -> Self {
+        Self {
+            bit_len: cursor.filled_len() * 8,
+            bit_pos: cursor.pos() * 8,
+            cursor,
+        }
+    }
+
+    ///Returns the underlying cursor with `pos` rounded up to the next byte.
+    pub fn into_cursor(mut self) -> Cursor<u8, N> {
+        unsafe { *self.cursor.pos_mut() = self.bit_pos.div_ceil(8) };
+        self.cursor
+    }
+
+    #[inline(always)]
+    pub fn cursor(&self) -> &Cursor<u8, N> {
+        &self.cursor
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.cursor.clear();
+        self.bit_len = 0;
+        self.bit_pos = 0;
+    }
+
+    #[inline(always)]
+    pub const fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    #[inline(always)]
+    pub const fn bit_pos(&self) -> usize {
+        self.bit_pos
+    }
+
+    #[inline(always)]
+    pub const fn remaining_bits(&self) -> usize {
+        self.bit_len - self.bit_pos
+    }
+
+    #[inline(always)]
+    pub const fn unfilled_bits(&self) -> usize {
+        N * 8 - self.bit_len
+    }
+
+    ///Writes the low `bits` of `value`, giving it back when it does not fit.
+    ///
+    ///# Panics
+    ///Panics if `bits` is greater than 64.
+    pub fn write_bits(&mut self, value: u64, bits: u32) -> Result<(), u64> {
+        assert!(bits <= u64::BITS, "can not write more than 64 bits at once");
+        if bits as usize > self.unfilled_bits() {
+            return Err(value);
+        }
+        let mut value = mask(value, bits);
+        let mut bits = bits as usize;
+        let buffer = self.cursor.as_array();
+        while bits != 0 {
+            let (index, offset) = (self.bit_len / 8, self.bit_len % 8);
+            if offset == 0 {
+                buffer[index] = 0;
+            }
+            let take = crate::min(8 - offset, bits);
+            buffer[index] |= (mask(value, take as u32) as u8) << offset;
+            value >>= take;
+            bits -= take;
+            self.bit_len += take;
+        }
+        unsafe { *self.cursor.filled_len_mut() = self.bit_len.div_ceil(8) };
+        Ok(())
+    }
+
+    ///Reads `bits` bits into the low bits of the result.
+    ///
+    ///# Panics
+    ///Panics if `bits` is greater than 64.
+    pub fn read_bits(&mut self, bits: u32) -> Option<u64> {
+        let value = self.peek_bits(bits)?;
+        self.bit_pos += bits as usize;
+        Some(value)
+    }
+
+    ///Same as [Self::read_bits] without advancing the read position.
+    pub fn peek_bits(&self, bits: u32) -> Option<u64> {
+        assert!(bits <= u64::BITS, "can not read more than 64 bits at once");
+        if bits as usize > self.remaining_bits() {
+            return None;
+        }
+        let filled = self.cursor.filled();
+        let mut bit_pos = self.bit_pos;
+        let mut value = 0;
+        let mut read = 0;
+        while read != bits as usize {
+            let (index, offset) = (bit_pos / 8, bit_pos % 8);
+            let take = crate::min(8 - offset, bits as usize - read);
+            let chunk = mask((filled[index] >> offset) as u64, take as u32);
+            value |= chunk << read;
+            read += take;
+            bit_pos += take;
+        }
+        Some(value)
+    }
+
+    #[inline(always)]
+    pub fn write_bool(&mut self, value: bool) -> Result<(), bool> {
+        self.write_bits(value as u64, 1).map_err(|_| value)
+    }
+
+    #[inline(always)]
+    pub fn read_bool(&mut self) -> Option<bool> {
+        self.read_bits(1).map(|bit| bit != 0)
+    }
+
+    ///Pads the written bits with zeros up to the next byte boundary.
+    #[inline(always)]
+    pub fn flush_to_byte(&mut self) {
+        self.bit_len = self.bit_len.div_ceil(8) * 8;
+    }
+
+    ///Skips the read position to the next byte boundary.
+    #[inline(always)]
+    pub fn align_read(&mut self) {
+        self.bit_pos = crate::min(self.bit_pos.div_ceil(8) * 8, self.bit_len);
+    }
+
+    ///Writes `bytes` bit-packed right after the current bit.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() * 8 > self.unfilled_bits() {
+            return Err(EncodeError::BufferFull);
+        }
+        for byte in bytes {
+            unsafe { self.write_bits(*byte as u64, 8).unwrap_unchecked() };
+        }
+        Ok(())
+    }
+
+    ///Flushes to the next byte boundary and copies `bytes` as whole bytes.
+    pub fn write_bytes_aligned(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let start = self.bit_len.div_ceil(8);
+        if bytes.len() > N - start {
+            return Err(EncodeError::BufferFull);
+        }
+        self.flush_to_byte();
+        self.cursor.as_array()[start..start + bytes.len()].copy_from_slice(bytes);
+        self.bit_len += bytes.len() * 8;
+        unsafe { *self.cursor.filled_len_mut() = self.bit_len / 8 };
+        Ok(())
+    }
+
+    ///Reads `buf.len()` bit-packed bytes from the current bit.
+    pub fn read_bytes(&mut self, buf: &mut [u8]) -> Result<(), DecodeError> {
+        if buf.len() * 8 > self.remaining_bits() {
+            return Err(DecodeError::Incomplete);
+        }
+        for byte in buf {
+            *byte = unsafe { self.read_bits(8).unwrap_unchecked() } as u8;
+        }
+        Ok(())
+    }
+
+    ///Skips to the next byte boundary and borrows `len` whole bytes.
+    pub fn read_bytes_aligned(&mut self, len: usize) -> Result<&[u8], DecodeError> {
+        let start = self.bit_pos.div_ceil(8);
+        if (start + len) * 8 > self.bit_len {
+            return Err(DecodeError::Incomplete);
+        }
+        self.bit_pos = (start + len) * 8;
+        Ok(&self.cursor.filled()[start..start + len])
+    }
+}
+
+#[inline(always)]
+const fn mask(value: u64, bits: u32) -> u64 {
+    if bits >= u64::BITS {
+        value
+    } else {
+        value & ((1 << bits) - 1)
+    }
+}
+
+impl<const N: usize> Default for BitCursor<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BitCursor;
+
+    #[test]
+    fn bits_round_trip() {
+        let mut cursor: BitCursor<16> = BitCursor::new();
+        cursor.write_bits(0b101, 3).unwrap();
+        cursor.write_bool(true).unwrap();
+        cursor.write_bits(0x3FF, 10).unwrap();
+        cursor.write_bits(u64::MAX, 64).unwrap();
+        cursor.write_bits(0xFF, 2).unwrap();
+        assert_eq!(cursor.bit_len(), 80);
+        assert_eq!(cursor.cursor().filled()[0], 0b1111_1101);
+        assert_eq!(cursor.read_bits(3), Some(0b101));
+        assert_eq!(cursor.read_bool(), Some(true));
+        assert_eq!(cursor.peek_bits(10), Some(0x3FF));
+        assert_eq!(cursor.read_bits(10), Some(0x3FF));
+        assert_eq!(cursor.read_bits(64), Some(u64::MAX));
+        assert_eq!(cursor.read_bits(3), None);
+        assert_eq!(cursor.read_bits(2), Some(0b11));
+        assert_eq!(cursor.write_bits(1, 49), Err(1));
+        assert_eq!(cursor.write_bits(0, 48), Ok(()));
+    }
+
+    #[test]
+    fn aligned() {
+        let mut cursor: BitCursor<8> = BitCursor::new();
+        cursor.write_bits(1, 1).unwrap();
+        cursor.write_bytes(&[0xAB]).unwrap();
+        cursor.write_bytes_aligned(&[1, 2]).unwrap();
+        assert_eq!(cursor.cursor().filled(), &[0x57, 0x01, 1, 2]);
+        cursor.write_bool(false).unwrap();
+        cursor.flush_to_byte();
+        assert_eq!(cursor.bit_len(), 40);
+        assert!(cursor.write_bytes_aligned(&[0; 4]).is_err());
+
+        assert_eq!(cursor.read_bits(1), Some(1));
+        let mut byte = [0];
+        cursor.read_bytes(&mut byte).unwrap();
+        assert_eq!(byte, [0xAB]);
+        assert_eq!(cursor.read_bytes_aligned(2), Ok(&[1u8, 2][..]));
+        let cursor = cursor.into_cursor();
+        assert_eq!(cursor.pos(), 4);
+        assert_eq!(cursor.filled_len(), 5);
+    }
+}
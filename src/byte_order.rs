@@ -0,0 +1,257 @@
+use core::mem::size_of;
+
+use crate::Cursor;
+
+///Primitive number that can be encoded with an explicit [ByteOrder].
+///# Safety
+///[Self::SIZE] must be the exact number of bytes the read and write methods access,
+///as safe methods such as [Cursor::read_primitive] bound their raw accesses by it.
+pub unsafe trait Primitive: Copy {
+    const SIZE: usize;
+
+    ///# Safety
+    ///`src` must be valid for reads of [Self::SIZE] bytes.
+    unsafe fn read_be(src: *const u8) -> Self;
+
+    ///# Safety
+    ///`src` must be valid for reads of [Self::SIZE] bytes.
+    unsafe fn read_le(src: *const u8) -> Self;
+
+    ///# Safety
+    ///`dst` must be valid for writes of [Self::SIZE] bytes.
+    unsafe fn write_be(self, dst: *mut u8);
+
+    ///# Safety
+    ///`dst` must be valid for writes of [Self::SIZE] bytes.
+    unsafe fn write_le(self, dst: *mut u8);
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {$(
+        unsafe impl Primitive for $ty {
+            const SIZE: usize = size_of::<$ty>();
+
+            #[inline(always)]
+            unsafe fn read_be(src: *const u8) -> Self {
+                <$ty>::from_be_bytes(src.cast::<[u8; size_of::<$ty>()]>().read_unaligned())
+            }
+
+            #[inline(always)]
+            unsafe fn read_le(src: *const u8) -> Self {
+                <$ty>::from_le_bytes(src.cast::<[u8; size_of::<$ty>()]>().read_unaligned())
+            }
+
+            #[inline(always)]
+            unsafe fn write_be(self, dst: *mut u8) {
+                dst.cast::<[u8; size_of::<$ty>()]>().write_unaligned(self.to_be_bytes())
+            }
+
+            #[inline(always)]
+            unsafe fn write_le(self, dst: *mut u8) {
+                dst.cast::<[u8; size_of::<$ty>()]>().write_unaligned(self.to_le_bytes())
+            }
+        }
+    )*};
+}
+
+impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+///Byte order used to encode a [Primitive], so codecs can be written once for both orders.
+pub trait ByteOrder {
+    ///# Safety
+    ///`src` must be valid for reads of `P::SIZE` bytes.
+    unsafe fn read<P: Primitive>(src: *const u8) -> P;
+
+    ///# Safety
+    ///`dst` must be valid for writes of `P::SIZE` bytes.
+    unsafe fn write<P: Primitive>(dst: *mut u8, value: P);
+}
+
+pub enum BigEndian {}
+
+pub enum LittleEndian {}
+
+pub type NetworkEndian = BigEndian;
+
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+impl ByteOrder for BigEndian {
+    #[inline(always)]
+    unsafe fn read<P: Primitive>(src: *const u8) -> P {
+        P::read_be(src)
+    }
+
+    #[inline(always)]
+    unsafe fn write<P: Primitive>(dst: *mut u8, value: P) {
+        value.write_be(dst)
+    }
+}
+
+impl ByteOrder for LittleEndian {
+    #[inline(always)]
+    unsafe fn read<P: Primitive>(src: *const u8) -> P {
+        P::read_le(src)
+    }
+
+    #[inline(always)]
+    unsafe fn write<P: Primitive>(dst: *mut u8, value: P) {
+        value.write_le(dst)
+    }
+}
+
+impl<const N: usize> Cursor<u8, N> {
+    #[inline(always)]
+    pub fn write_primitive<B: ByteOrder, P: Primitive>(&mut self, value: P) -> Result<(), P> {
+        if self.filled_len() + P::SIZE <= N {
+            unsafe { self.write_primitive_unchecked::<B, P>(value) };
+            Ok(())
+        } else {
+            Err(value)
+        }
+    }
+
+    ///# Safety
+    ///The unfilled region must have room for `P::SIZE` bytes.
+    #[inline(always)]
+    pub unsafe fn write_primitive_unchecked<B: ByteOrder, P: Primitive>(&mut self, value: P) {
+        B::write(self.unfilled_mut().as_mut_ptr(), value);
+        *self.filled_len_mut() += P::SIZE;
+    }
+
+    #[inline(always)]
+    pub fn read_primitive<B: ByteOrder, P: Primitive>(&mut self) -> Option<P> {
+        if self.remaining() >= P::SIZE {
+            Some(unsafe { self.read_primitive_unchecked::<B, P>() })
+        } else {
+            None
+        }
+    }
+
+    ///# Safety
+    ///At least `P::SIZE` bytes must remain unread.
+    #[inline(always)]
+    pub unsafe fn read_primitive_unchecked<B: ByteOrder, P: Primitive>(&mut self) -> P {
+        let pos = self.pos();
+        let value = B::read(self.filled().as_ptr().add(pos));
+        *self.pos_mut() = pos + P::SIZE;
+        value
+    }
+
+    ///Reads without advancing `pos`.
+    #[inline(always)]
+    pub fn peek_primitive<B: ByteOrder, P: Primitive>(&self) -> Option<P> {
+        if self.remaining() >= P::SIZE {
+            Some(unsafe { B::read(self.filled().as_ptr().add(self.pos())) })
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_cursor_methods {
+    ($($ty:ty, $order:ty => $write:ident, $write_unchecked:ident, $read:ident, $read_unchecked:ident, $peek:ident;)*) => {
+        impl<const N: usize> Cursor<u8, N> {$(
+            #[inline(always)]
+            pub fn $write(&mut self, value: $ty) -> Result<(), $ty> {
+                self.write_primitive::<$order, $ty>(value)
+            }
+
+            ///# Safety
+            ///See [Self::write_primitive_unchecked].
+            #[inline(always)]
+            pub unsafe fn $write_unchecked(&mut self, value: $ty) {
+                self.write_primitive_unchecked::<$order, $ty>(value)
+            }
+
+            #[inline(always)]
+            pub fn $read(&mut self) -> Option<$ty> {
+                self.read_primitive::<$order, $ty>()
+            }
+
+            ///# Safety
+            ///See [Self::read_primitive_unchecked].
+            #[inline(always)]
+            pub unsafe fn $read_unchecked(&mut self) -> $ty {
+                self.read_primitive_unchecked::<$order, $ty>()
+            }
+
+            #[inline(always)]
+            pub fn $peek(&self) -> Option<$ty> {
+                self.peek_primitive::<$order, $ty>()
+            }
+        )*}
+    };
+}
+
+impl_cursor_methods! {
+    u8, BigEndian => write_u8, write_u8_unchecked, read_u8, read_u8_unchecked, peek_u8;
+    i8, BigEndian => write_i8, write_i8_unchecked, read_i8, read_i8_unchecked, peek_i8;
+    u16, BigEndian => write_u16_be, write_u16_be_unchecked, read_u16_be, read_u16_be_unchecked, peek_u16_be;
+    u16, LittleEndian => write_u16_le, write_u16_le_unchecked, read_u16_le, read_u16_le_unchecked, peek_u16_le;
+    u32, BigEndian => write_u32_be, write_u32_be_unchecked, read_u32_be, read_u32_be_unchecked, peek_u32_be;
+    u32, LittleEndian => write_u32_le, write_u32_le_unchecked, read_u32_le, read_u32_le_unchecked, peek_u32_le;
+    u64, BigEndian => write_u64_be, write_u64_be_unchecked, read_u64_be, read_u64_be_unchecked, peek_u64_be;
+    u64, LittleEndian => write_u64_le, write_u64_le_unchecked, read_u64_le, read_u64_le_unchecked, peek_u64_le;
+    u128, BigEndian => write_u128_be, write_u128_be_unchecked, read_u128_be, read_u128_be_unchecked, peek_u128_be;
+    u128, LittleEndian => write_u128_le, write_u128_le_unchecked, read_u128_le, read_u128_le_unchecked, peek_u128_le;
+    i16, BigEndian => write_i16_be, write_i16_be_unchecked, read_i16_be, read_i16_be_unchecked, peek_i16_be;
+    i16, LittleEndian => write_i16_le, write_i16_le_unchecked, read_i16_le, read_i16_le_unchecked, peek_i16_le;
+    i32, BigEndian => write_i32_be, write_i32_be_unchecked, read_i32_be, read_i32_be_unchecked, peek_i32_be;
+    i32, LittleEndian => write_i32_le, write_i32_le_unchecked, read_i32_le, read_i32_le_unchecked, peek_i32_le;
+    i64, BigEndian => write_i64_be, write_i64_be_unchecked, read_i64_be, read_i64_be_unchecked, peek_i64_be;
+    i64, LittleEndian => write_i64_le, write_i64_le_unchecked, read_i64_le, read_i64_le_unchecked, peek_i64_le;
+    i128, BigEndian => write_i128_be, write_i128_be_unchecked, read_i128_be, read_i128_be_unchecked, peek_i128_be;
+    i128, LittleEndian => write_i128_le, write_i128_le_unchecked, read_i128_le, read_i128_le_unchecked, peek_i128_le;
+    f32, BigEndian => write_f32_be, write_f32_be_unchecked, read_f32_be, read_f32_be_unchecked, peek_f32_be;
+    f32, LittleEndian => write_f32_le, write_f32_le_unchecked, read_f32_le, read_f32_le_unchecked, peek_f32_le;
+    f64, BigEndian => write_f64_be, write_f64_be_unchecked, read_f64_be, read_f64_be_unchecked, peek_f64_be;
+    f64, LittleEndian => write_f64_le, write_f64_le_unchecked, read_f64_le, read_f64_le_unchecked, peek_f64_le;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BigEndian, ByteOrder, Cursor, LittleEndian, Primitive};
+
+    #[test]
+    fn big_and_little_endian() {
+        let mut cursor: Cursor<u8, 32> = Cursor::new();
+        cursor.write_u16_be(0x0102).unwrap();
+        cursor.write_u32_le(0x03040506).unwrap();
+        cursor.write_f64_be(1.5).unwrap();
+        assert_eq!(&cursor.filled()[..6], &[1, 2, 6, 5, 4, 3]);
+        assert_eq!(&cursor.filled()[6..], &1.5f64.to_be_bytes());
+        assert_eq!(cursor.peek_u16_be(), Some(0x0102));
+        assert_eq!(cursor.read_u16_be(), Some(0x0102));
+        assert_eq!(cursor.read_u32_le(), Some(0x03040506));
+        assert_eq!(cursor.read_f64_be(), Some(1.5));
+        assert_eq!(cursor.read_u8(), None);
+    }
+
+    #[test]
+    fn bounds() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        cursor.write_u128_be(u128::MAX - 1).unwrap();
+        assert_eq!(cursor.write_u8(1), Err(1));
+        assert_eq!(cursor.read_u64_le(), Some(u64::MAX));
+        assert_eq!(cursor.read_u128_be(), None);
+        assert_eq!(cursor.read_i64_be(), Some(-2));
+    }
+
+    #[test]
+    fn generic_byte_order() {
+        fn round_trip<B: ByteOrder, P: Primitive + PartialEq + core::fmt::Debug>(value: P) {
+            let mut cursor: Cursor<u8, 16> = Cursor::new();
+            cursor.write_primitive::<B, P>(value).unwrap();
+            assert_eq!(cursor.filled_len(), P::SIZE);
+            assert_eq!(cursor.read_primitive::<B, P>(), Some(value));
+        }
+        round_trip::<BigEndian, _>(-12345i32);
+        round_trip::<LittleEndian, _>(-12345i32);
+        round_trip::<LittleEndian, _>(0.25f32);
+        round_trip::<BigEndian, _>(i128::MIN);
+    }
+}
@@ -0,0 +1,378 @@
+use core::ops::Range;
+
+use crate::{Cursor, Mark};
+
+///Incremental checksum or hash, fed bytes in any number of chunks.
+pub trait Checksum {
+    type Output;
+
+    fn update(&mut self, bytes: &[u8]);
+
+    ///Result over every byte fed so far, leaving the state untouched.
+    fn finish(&self) -> Self::Output;
+}
+
+impl<const N: usize> Cursor<u8, N> {
+    ///Checksum of the filled region.
+    #[inline(always)]
+    pub fn checksum<C: Checksum>(&self, mut checksum: C) -> C::Output {
+        checksum.update(self.filled());
+        checksum.finish()
+    }
+
+    ///Checksum of `range` of the filled region, [None] if it is out of bounds.
+    #[inline(always)]
+    pub fn checksum_range<C: Checksum>(
+        &self,
+        mut checksum: C,
+        range: Range<usize>,
+    ) -> Option<C::Output> {
+        checksum.update(self.filled().get(range)?);
+        Some(checksum.finish())
+    }
+
+    ///Feeds the bytes pushed since `from` into `checksum`, returning where the next update starts.
+    ///Start with `cursor.mark_at(0)`. Compacting the cursor in between is followed, but [None]
+    ///is returned without touching `checksum` once bytes it has not seen were dropped,
+    ///as by [Cursor::clear], [Cursor::drain] or compacting past `from`; start over with a fresh checksum then.
+    #[inline(always)]
+    pub fn update_checksum<C: Checksum>(&self, checksum: &mut C, from: Mark) -> Option<Mark> {
+        let from = self.mark_index(from)?;
+        checksum.update(&self.filled()[from..]);
+        Some(self.mark_at(self.filled_len()))
+    }
+}
+
+const fn crc32_table(poly: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+static CRC32_IEEE: [u32; 256] = crc32_table(0xEDB88320);
+static CRC32_CASTAGNOLI: [u32; 256] = crc32_table(0x82F63B78);
+
+///Reflected CRC-32 with either the IEEE (zlib, Ethernet) or the Castagnoli (iSCSI) polynomial.
+#[derive(Clone, Copy)]
+pub struct Crc32 {
+    table: &'static [u32; 256],
+    crc: u32,
+}
+
+impl Crc32 {
+    #[inline(always)]
+    pub fn ieee() -> Self {
+        Self {
+            table: &CRC32_IEEE,
+            crc: !0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn castagnoli() -> Self {
+        Self {
+            table: &CRC32_CASTAGNOLI,
+            crc: !0,
+        }
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    #[inline(always)]
+    fn update(&mut self, bytes: &[u8]) {
+        let mut crc = self.crc;
+        for byte in bytes {
+            crc = self.table[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+        }
+        self.crc = crc;
+    }
+
+    #[inline(always)]
+    fn finish(&self) -> u32 {
+        !self.crc
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    const MOD: u32 = 65521;
+    ///Most bytes summed before `b` could overflow.
+    const NMAX: usize = 5552;
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(Self::NMAX) {
+            for byte in chunk {
+                self.a += *byte as u32;
+                self.b += self.a;
+            }
+            self.a %= Self::MOD;
+            self.b %= Self::MOD;
+        }
+    }
+
+    #[inline(always)]
+    fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+const PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const PRIME64_3: u64 = 0x165667B19E3779F9;
+const PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+///Streaming XXH64.
+#[derive(Clone, Copy)]
+pub struct XxHash64 {
+    seed: u64,
+    lanes: [u64; 4],
+    buffer: [u8; 32],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+impl XxHash64 {
+    pub const fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            lanes: [
+                seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+                seed.wrapping_add(PRIME64_2),
+                seed,
+                seed.wrapping_sub(PRIME64_1),
+            ],
+            buffer: [0; 32],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    #[inline(always)]
+    fn consume_stripe(&mut self, stripe: &[u8]) {
+        for (lane, bytes) in self.lanes.iter_mut().zip(stripe.chunks_exact(8)) {
+            *lane = xxh64_round(*lane, read_u64(bytes));
+        }
+    }
+}
+
+impl Default for XxHash64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline(always)]
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+#[inline(always)]
+const fn xxh64_round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+#[inline(always)]
+const fn xxh64_merge(acc: u64, lane: u64) -> u64 {
+    (acc ^ xxh64_round(0, lane))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+impl Checksum for XxHash64 {
+    type Output = u64;
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        self.total_len += bytes.len() as u64;
+        if self.buffer_len != 0 {
+            let len = crate::min(32 - self.buffer_len, bytes.len());
+            self.buffer[self.buffer_len..self.buffer_len + len].copy_from_slice(&bytes[..len]);
+            self.buffer_len += len;
+            bytes = &bytes[len..];
+            if self.buffer_len < 32 {
+                return;
+            }
+            let buffer = self.buffer;
+            self.consume_stripe(&buffer);
+            self.buffer_len = 0;
+        }
+        let mut stripes = bytes.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.consume_stripe(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    fn finish(&self) -> u64 {
+        let [v1, v2, v3, v4] = self.lanes;
+        let mut hash = if self.total_len >= 32 {
+            let mut hash = v1
+                .rotate_left(1)
+                .wrapping_add(v2.rotate_left(7))
+                .wrapping_add(v3.rotate_left(12))
+                .wrapping_add(v4.rotate_left(18));
+            for lane in self.lanes {
+                hash = xxh64_merge(hash, lane);
+            }
+            hash
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+        hash = hash.wrapping_add(self.total_len);
+        let mut rest = &self.buffer[..self.buffer_len];
+        while rest.len() >= 8 {
+            hash ^= xxh64_round(0, read_u64(rest));
+            hash = hash
+                .rotate_left(27)
+                .wrapping_mul(PRIME64_1)
+                .wrapping_add(PRIME64_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            let value = u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64;
+            hash ^= value.wrapping_mul(PRIME64_1);
+            hash = hash
+                .rotate_left(23)
+                .wrapping_mul(PRIME64_2)
+                .wrapping_add(PRIME64_3);
+            rest = &rest[4..];
+        }
+        for byte in rest {
+            hash ^= (*byte as u64).wrapping_mul(PRIME64_5);
+            hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+        }
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(PRIME64_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(PRIME64_3);
+        hash ^ (hash >> 32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Adler32, Checksum, Crc32, XxHash64};
+    use crate::Cursor;
+
+    fn cursor(bytes: &[u8]) -> Cursor<u8, 128> {
+        let mut cursor = Cursor::new();
+        for byte in bytes {
+            cursor.push(*byte).unwrap();
+        }
+        cursor
+    }
+
+    #[test]
+    fn known_vectors() {
+        let check = cursor(b"123456789");
+        assert_eq!(check.checksum(Crc32::ieee()), 0xCBF43926);
+        assert_eq!(check.checksum(Crc32::castagnoli()), 0xE3069283);
+        assert_eq!(cursor(b"Wikipedia").checksum(Adler32::new()), 0x11E60398);
+        assert_eq!(cursor(b"").checksum(XxHash64::new()), 0xEF46DB3751D8E999);
+        assert_eq!(cursor(b"a").checksum(XxHash64::new()), 0xD24EC4F1A98C6E5B);
+        assert_eq!(cursor(b"abc").checksum(XxHash64::new()), 0x44BC2CF5AD770999);
+        assert_eq!(
+            cursor(b"Nobody inspects the spammish repetition").checksum(XxHash64::new()),
+            0xFBCEA83C8A378BF1
+        );
+        assert_eq!(check.checksum_range(Crc32::ieee(), 0..9), Some(0xCBF43926));
+        assert_eq!(check.checksum_range(Crc32::ieee(), 0..10), None);
+    }
+
+    #[test]
+    fn incremental() {
+        let bytes: [u8; 100] = core::array::from_fn(|i| (i * 7) as u8);
+        let mut cursor: Cursor<u8, 128> = Cursor::new();
+        let mut crc = Crc32::castagnoli();
+        let mut adler = Adler32::new();
+        let mut xxh = XxHash64::with_seed(42);
+        let start = cursor.mark_at(0);
+        let (mut crc_from, mut adler_from, mut xxh_from) = (start, start, start);
+        for chunk in bytes.chunks(13) {
+            for byte in chunk {
+                cursor.push(*byte).unwrap();
+            }
+            crc_from = cursor.update_checksum(&mut crc, crc_from).unwrap();
+            adler_from = cursor.update_checksum(&mut adler, adler_from).unwrap();
+            xxh_from = cursor.update_checksum(&mut xxh, xxh_from).unwrap();
+        }
+        assert_eq!(crc.finish(), cursor.checksum(Crc32::castagnoli()));
+        assert_eq!(adler.finish(), cursor.checksum(Adler32::new()));
+        assert_eq!(xxh.finish(), cursor.checksum(XxHash64::with_seed(42)));
+        cursor.push(0).unwrap();
+        cursor.clear();
+        let mut crc = Crc32::castagnoli();
+        assert_eq!(cursor.update_checksum(&mut crc, crc_from), None);
+        assert_eq!(crc.finish(), Crc32::castagnoli().finish());
+        let mut adler = Adler32::new();
+        adler.update(&[0xFF; 20000]);
+        assert_eq!(adler.finish(), 0x9F51D664);
+    }
+
+    #[test]
+    fn incremental_compact() {
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        let mut crc = Crc32::ieee();
+        cursor.write_u32_be(1).unwrap();
+        let from = cursor.update_checksum(&mut crc, cursor.mark_at(0)).unwrap();
+        cursor.read_u16_be().unwrap();
+        cursor.compact();
+        cursor.write_u16_be(2).unwrap();
+        let from = cursor.update_checksum(&mut crc, from).unwrap();
+        let mut whole = Crc32::ieee();
+        whole.update(&[0, 0, 0, 1, 0, 2]);
+        assert_eq!(crc.finish(), whole.finish());
+
+        cursor.write_u16_be(3).unwrap();
+        cursor.read_value::<[u8; 6]>().unwrap();
+        cursor.compact();
+        cursor.write_u32_be(4).unwrap();
+        assert_eq!(cursor.update_checksum(&mut crc, from), None);
+        assert_eq!(crc.finish(), whole.finish());
+    }
+}
@@ -0,0 +1,392 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::{Cursor, Mark};
+
+///In-place transform over a byte stream, such as a stream cipher.
+///Each byte must be fed exactly once and in order.
+pub trait StreamTransform {
+    fn apply(&mut self, bytes: &mut [u8]);
+}
+
+///Byte cursor that remembers how much of its filled region has been run through a
+///[StreamTransform], so each byte is transformed exactly once.
+///Dereferences to the wrapped [Cursor], whose clearing, compaction and draining it follows.
+pub struct TransformCursor<const N: usize> {
+    cursor: Cursor<u8, N>,
+    ///End of the transformed prefix.
+    transformed: Mark,
+}
+
+impl<const N: usize> TransformCursor<N> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::from(Cursor::new())
+    }
+
+    ///Length of the filled prefix already run through a [StreamTransform].
+    #[inline(always)]
+    pub fn transformed_len(&self) -> usize {
+        self.cursor.mark_index_clamped(self.transformed)
+    }
+
+    ///Runs `transform` over the filled bytes it has not seen yet.
+    pub fn apply_transform<S: StreamTransform>(&mut self, transform: &mut S) {
+        let start = self.transformed_len();
+        unsafe { transform.apply(&mut self.cursor.filled_mut()[start..]) };
+        self.transformed = self.cursor.mark_at(self.cursor.filled_len());
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> Cursor<u8, N> {
+        self.cursor
+    }
+}
+
+impl<const N: usize> From<Cursor<u8, N>> for TransformCursor<N> {
+    ///None of the filled bytes count as transformed.
+    fn from(cursor: Cursor<u8, N>) -> Self {
+        Self {
+            transformed: cursor.mark_at(0),
+            cursor,
+        }
+    }
+}
+
+impl<const N: usize> Default for TransformCursor<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for TransformCursor<N> {
+    type Target = Cursor<u8, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cursor
+    }
+}
+
+impl<const N: usize> DerefMut for TransformCursor<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cursor
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> TransformCursor<N> {
+    ///Same as [Cursor::push_from_read], then runs `transform` over the bytes read,
+    ///such as decrypting inbound traffic.
+    pub fn push_from_read_transform<R: std::io::Read, S: StreamTransform>(
+        &mut self,
+        read: &mut R,
+        transform: &mut S,
+    ) -> std::io::Result<usize> {
+        let read_length = self.cursor.push_from_read(read)?;
+        self.apply_transform(transform);
+        Ok(read_length)
+    }
+
+    ///Runs `transform` over the bytes pushed since the last call, such as encrypting outbound
+    ///traffic, then continues as [Cursor::push_to_write]. Bytes left unwritten are not transformed again.
+    pub fn push_to_write_transform<W: std::io::Write, S: StreamTransform>(
+        &mut self,
+        write: &mut W,
+        transform: &mut S,
+    ) -> std::io::Result<usize> {
+        self.apply_transform(transform);
+        self.cursor.push_to_write(write)
+    }
+}
+
+const fn sbox() -> [u8; 256] {
+    let mut sbox = [0; 256];
+    let mut p: u8 = 1;
+    let mut q: u8 = 1;
+    loop {
+        p = p ^ (p << 1) ^ if p & 0x80 != 0 { 0x1B } else { 0 };
+        q ^= q << 1;
+        q ^= q << 2;
+        q ^= q << 4;
+        if q & 0x80 != 0 {
+            q ^= 0x09;
+        }
+        let x = q ^ q.rotate_left(1) ^ q.rotate_left(2) ^ q.rotate_left(3) ^ q.rotate_left(4);
+        sbox[p as usize] = x ^ 0x63;
+        if p == 1 {
+            break;
+        }
+    }
+    sbox[0] = 0x63;
+    sbox
+}
+
+static SBOX: [u8; 256] = sbox();
+
+#[inline(always)]
+const fn xtime(value: u8) -> u8 {
+    (value << 1) ^ if value & 0x80 != 0 { 0x1B } else { 0 }
+}
+
+///AES-128 block encryption, the building block of the CFB8 stream modes.
+///
+///# Security
+///This is a table-based implementation: the S-box lookups index memory with secret-dependent
+///bytes and are not constant-time, so key material can leak through cache timing to an attacker
+///sharing the machine. Use a hardened implementation where that matters.
+#[derive(Clone)]
+pub struct Aes128 {
+    round_keys: [[u8; 16]; 11],
+}
+
+impl Aes128 {
+    pub fn new(key: [u8; 16]) -> Self {
+        let mut words = [[0u8; 4]; 44];
+        for (i, word) in words.iter_mut().take(4).enumerate() {
+            word.copy_from_slice(&key[i * 4..i * 4 + 4]);
+        }
+        let mut rcon = 1;
+        for i in 4..44 {
+            let mut word = words[i - 1];
+            if i % 4 == 0 {
+                word = [
+                    SBOX[word[1] as usize] ^ rcon,
+                    SBOX[word[2] as usize],
+                    SBOX[word[3] as usize],
+                    SBOX[word[0] as usize],
+                ];
+                rcon = xtime(rcon);
+            }
+            for j in 0..4 {
+                words[i][j] = words[i - 4][j] ^ word[j];
+            }
+        }
+        let mut round_keys = [[0; 16]; 11];
+        for (round, round_key) in round_keys.iter_mut().enumerate() {
+            for column in 0..4 {
+                round_key[column * 4..column * 4 + 4].copy_from_slice(&words[round * 4 + column]);
+            }
+        }
+        Self { round_keys }
+    }
+
+    pub fn encrypt_block(&self, block: &mut [u8; 16]) {
+        add_round_key(block, &self.round_keys[0]);
+        for round_key in &self.round_keys[1..10] {
+            sub_bytes_shift_rows(block);
+            mix_columns(block);
+            add_round_key(block, round_key);
+        }
+        sub_bytes_shift_rows(block);
+        add_round_key(block, &self.round_keys[10]);
+    }
+}
+
+#[inline(always)]
+fn add_round_key(block: &mut [u8; 16], round_key: &[u8; 16]) {
+    for (byte, key) in block.iter_mut().zip(round_key) {
+        *byte ^= key;
+    }
+}
+
+#[inline(always)]
+fn sub_bytes_shift_rows(block: &mut [u8; 16]) {
+    let state = *block;
+    for column in 0..4 {
+        for row in 0..4 {
+            block[row + column * 4] = SBOX[state[row + (column + row) % 4 * 4] as usize];
+        }
+    }
+}
+
+#[inline(always)]
+fn mix_columns(block: &mut [u8; 16]) {
+    for column in block.chunks_exact_mut(4) {
+        let [a0, a1, a2, a3] = [column[0], column[1], column[2], column[3]];
+        column[0] = xtime(a0) ^ xtime(a1) ^ a1 ^ a2 ^ a3;
+        column[1] = a0 ^ xtime(a1) ^ xtime(a2) ^ a2 ^ a3;
+        column[2] = a0 ^ a1 ^ xtime(a2) ^ xtime(a3) ^ a3;
+        column[3] = xtime(a0) ^ a0 ^ a1 ^ a2 ^ xtime(a3);
+    }
+}
+
+///Shift register shared by both CFB8 directions.
+#[derive(Clone)]
+struct Cfb8 {
+    cipher: Aes128,
+    register: [u8; 16],
+}
+
+impl Cfb8 {
+    ///Returns the next keystream byte and shifts `ciphertext` into the register.
+    #[inline(always)]
+    fn next(&mut self, input: u8, encrypt: bool) -> u8 {
+        let mut block = self.register;
+        self.cipher.encrypt_block(&mut block);
+        let output = input ^ block[0];
+        let ciphertext = if encrypt { output } else { input };
+        self.register.copy_within(1.., 0);
+        self.register[15] = ciphertext;
+        output
+    }
+}
+
+///AES-128 in CFB8 mode encrypting in place, as used by Minecraft connections.
+///Not constant-time, see [Aes128].
+#[derive(Clone)]
+pub struct Aes128Cfb8Encrypt(Cfb8);
+
+///AES-128 in CFB8 mode decrypting in place. Not constant-time, see [Aes128].
+#[derive(Clone)]
+pub struct Aes128Cfb8Decrypt(Cfb8);
+
+impl Aes128Cfb8Encrypt {
+    pub fn new(key: [u8; 16], iv: [u8; 16]) -> Self {
+        Self(Cfb8 {
+            cipher: Aes128::new(key),
+            register: iv,
+        })
+    }
+}
+
+impl Aes128Cfb8Decrypt {
+    pub fn new(key: [u8; 16], iv: [u8; 16]) -> Self {
+        Self(Cfb8 {
+            cipher: Aes128::new(key),
+            register: iv,
+        })
+    }
+}
+
+impl StreamTransform for Aes128Cfb8Encrypt {
+    fn apply(&mut self, bytes: &mut [u8]) {
+        for byte in bytes {
+            *byte = self.0.next(*byte, true);
+        }
+    }
+}
+
+impl StreamTransform for Aes128Cfb8Decrypt {
+    fn apply(&mut self, bytes: &mut [u8]) {
+        for byte in bytes {
+            *byte = self.0.next(*byte, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Aes128, Aes128Cfb8Decrypt, Aes128Cfb8Encrypt, StreamTransform, TransformCursor};
+
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+    const IV: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    const PLAINTEXT: [u8; 18] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a, 0xae, 0x2d,
+    ];
+    const CIPHERTEXT: [u8; 18] = [
+        0x3b, 0x79, 0x42, 0x4c, 0x9c, 0x0d, 0xd4, 0x36, 0xba, 0xce, 0x9e, 0x0e, 0xd4, 0x58, 0x6a,
+        0x4f, 0x32, 0xb9,
+    ];
+
+    #[test]
+    fn aes128_block() {
+        //FIPS-197 appendix C.1
+        let cipher = Aes128::new(core::array::from_fn(|i| i as u8));
+        let mut block = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        cipher.encrypt_block(&mut block);
+        assert_eq!(
+            block,
+            [
+                0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4,
+                0xc5, 0x5a
+            ]
+        );
+    }
+
+    #[test]
+    fn cfb8_vectors() {
+        //NIST SP 800-38A F.3.7 and F.3.8
+        let mut bytes = PLAINTEXT;
+        let mut encrypt = Aes128Cfb8Encrypt::new(KEY, IV);
+        encrypt.apply(&mut bytes[..5]);
+        encrypt.apply(&mut bytes[5..]);
+        assert_eq!(bytes, CIPHERTEXT);
+        Aes128Cfb8Decrypt::new(KEY, IV).apply(&mut bytes);
+        assert_eq!(bytes, PLAINTEXT);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn transform_once() {
+        let mut outgoing: TransformCursor<32> = TransformCursor::new();
+        let mut encrypt = Aes128Cfb8Encrypt::new(KEY, IV);
+        let mut wire = [0u8; 18];
+        for byte in &PLAINTEXT[..10] {
+            outgoing.push(*byte).unwrap();
+        }
+        let mut first = &mut wire[..4];
+        assert_eq!(
+            outgoing
+                .push_to_write_transform(&mut first, &mut encrypt)
+                .unwrap(),
+            4
+        );
+        for byte in &PLAINTEXT[10..] {
+            outgoing.push(*byte).unwrap();
+        }
+        assert_eq!(outgoing.transformed_len(), 10);
+        let mut rest = &mut wire[4..];
+        assert_eq!(
+            outgoing
+                .push_to_write_transform(&mut rest, &mut encrypt)
+                .unwrap(),
+            14
+        );
+        assert_eq!(wire, CIPHERTEXT);
+        assert_eq!(outgoing.transformed_len(), 0);
+
+        let mut incoming: TransformCursor<32> = TransformCursor::new();
+        let mut decrypt = Aes128Cfb8Decrypt::new(KEY, IV);
+        for chunk in wire.chunks(7) {
+            incoming
+                .push_from_read_transform(&mut &chunk[..], &mut decrypt)
+                .unwrap();
+        }
+        assert_eq!(incoming.filled(), &PLAINTEXT);
+        incoming.read_value::<[u8; 8]>().unwrap();
+        incoming.compact();
+        assert_eq!(incoming.transformed_len(), 10);
+    }
+
+    #[test]
+    fn follows_cursor() {
+        let mut cursor: TransformCursor<16> = TransformCursor::new();
+        let mut encrypt = Aes128Cfb8Encrypt::new(KEY, IV);
+        for byte in &PLAINTEXT[..6] {
+            cursor.push(*byte).unwrap();
+        }
+        cursor.apply_transform(&mut encrypt);
+        cursor.clear();
+        assert_eq!(cursor.transformed_len(), 0);
+        for byte in &PLAINTEXT[..6] {
+            cursor.push(*byte).unwrap();
+        }
+        assert_eq!(cursor.transformed_len(), 0);
+        cursor.apply_transform(&mut encrypt);
+        cursor.read_value::<[u8; 2]>().unwrap();
+        assert_eq!(cursor.drain().count(), 4);
+        assert_eq!(cursor.transformed_len(), 2);
+        cursor.push(0).unwrap();
+        assert_eq!(cursor.transformed_len(), 2);
+        cursor.compact();
+        assert_eq!(cursor.transformed_len(), 0);
+        assert_eq!(cursor.filled_len(), 1);
+    }
+}
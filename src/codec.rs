@@ -0,0 +1,295 @@
+use core::marker::PhantomData;
+
+use crate::{varint_len, BigEndian, ByteOrder, Cursor, String, VarIntError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    ///The buffer ends before the value does, retry once more bytes are pushed.
+    Incomplete,
+    ///The bytes can never decode into the value, such as an overlong VarInt.
+    Malformed,
+    InvalidUtf8,
+    ///The value does not fit in the destination capacity.
+    Capacity,
+}
+
+impl From<VarIntError> for DecodeError {
+    fn from(value: VarIntError) -> Self {
+        match value {
+            VarIntError::Incomplete => DecodeError::Incomplete,
+            VarIntError::TooLong => DecodeError::Malformed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    ///The cursor has no room left for the value.
+    BufferFull,
+    ///The length can not be represented by the length prefix.
+    LengthOverflow,
+    ///The cursor was cleared or compacted under a reserved [crate::Slot].
+    SlotInvalidated,
+}
+
+///Encoding of the length written before strings, byte arrays and frames.
+pub trait LengthPrefix {
+    ///Largest length the prefix can represent.
+    const MAX_LEN: usize;
+    ///Largest number of bytes the prefix itself takes.
+    const MAX_SIZE: usize;
+
+    ///Number of bytes the prefix takes for `len`.
+    fn size(len: usize) -> usize;
+
+    fn write<const N: usize>(cursor: &mut Cursor<u8, N>, len: usize) -> Result<(), EncodeError>;
+
+    fn read<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<usize, DecodeError>;
+}
+
+pub struct PrefixU8;
+
+pub struct PrefixU16<B: ByteOrder = BigEndian>(PhantomData<B>);
+
+pub struct PrefixU32<B: ByteOrder = BigEndian>(PhantomData<B>);
+
+pub struct PrefixVarInt;
+
+macro_rules! impl_fixed_prefix {
+    ($($prefix:ty, $ty:ty;)*) => {$(
+        impl<B: ByteOrder> LengthPrefix for $prefix {
+            const MAX_LEN: usize = <$ty>::MAX as usize;
+            const MAX_SIZE: usize = core::mem::size_of::<$ty>();
+
+            #[inline(always)]
+            fn size(_len: usize) -> usize {
+                Self::MAX_SIZE
+            }
+
+            #[inline(always)]
+            fn write<const N: usize>(cursor: &mut Cursor<u8, N>, len: usize) -> Result<(), EncodeError> {
+                if len > Self::MAX_LEN {
+                    return Err(EncodeError::LengthOverflow);
+                }
+                cursor
+                    .write_primitive::<B, $ty>(len as $ty)
+                    .map_err(|_| EncodeError::BufferFull)
+            }
+
+            #[inline(always)]
+            fn read<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<usize, DecodeError> {
+                cursor
+                    .read_primitive::<B, $ty>()
+                    .map(|len| len as usize)
+                    .ok_or(DecodeError::Incomplete)
+            }
+        }
+    )*};
+}
+
+impl_fixed_prefix! {
+    PrefixU16<B>, u16;
+    PrefixU32<B>, u32;
+}
+
+impl LengthPrefix for PrefixU8 {
+    const MAX_LEN: usize = u8::MAX as usize;
+    const MAX_SIZE: usize = 1;
+
+    #[inline(always)]
+    fn size(_len: usize) -> usize {
+        1
+    }
+
+    #[inline(always)]
+    fn write<const N: usize>(cursor: &mut Cursor<u8, N>, len: usize) -> Result<(), EncodeError> {
+        if len > Self::MAX_LEN {
+            return Err(EncodeError::LengthOverflow);
+        }
+        cursor
+            .write_u8(len as u8)
+            .map_err(|_| EncodeError::BufferFull)
+    }
+
+    #[inline(always)]
+    fn read<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<usize, DecodeError> {
+        cursor
+            .read_u8()
+            .map(|len| len as usize)
+            .ok_or(DecodeError::Incomplete)
+    }
+}
+
+impl LengthPrefix for PrefixVarInt {
+    const MAX_LEN: usize = i32::MAX as usize;
+    const MAX_SIZE: usize = 5;
+
+    #[inline(always)]
+    fn size(len: usize) -> usize {
+        varint_len(len as i32)
+    }
+
+    #[inline(always)]
+    fn write<const N: usize>(cursor: &mut Cursor<u8, N>, len: usize) -> Result<(), EncodeError> {
+        if len > Self::MAX_LEN {
+            return Err(EncodeError::LengthOverflow);
+        }
+        cursor
+            .write_varint_i32(len as i32)
+            .map_err(|_| EncodeError::BufferFull)
+    }
+
+    #[inline(always)]
+    fn read<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<usize, DecodeError> {
+        let len = cursor.read_varint_i32()?;
+        if len < 0 {
+            Err(DecodeError::Malformed)
+        } else {
+            Ok(len as usize)
+        }
+    }
+}
+
+impl<const N: usize> Cursor<u8, N> {
+    ///Writes `bytes` after its length, leaving the cursor untouched on error.
+    pub fn write_bytes_prefixed<P: LengthPrefix>(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), EncodeError> {
+        let len = bytes.len();
+        if len > P::MAX_LEN {
+            return Err(EncodeError::LengthOverflow);
+        }
+        if P::size(len) + len > N - self.filled_len() {
+            return Err(EncodeError::BufferFull);
+        }
+        P::write(self, len)?;
+        unsafe {
+            self.unfilled_mut()[..len].copy_from_slice(bytes);
+            *self.filled_len_mut() += len;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn write_str_prefixed<P: LengthPrefix>(&mut self, value: &str) -> Result<(), EncodeError> {
+        self.write_bytes_prefixed::<P>(value.as_bytes())
+    }
+
+    ///Borrows length-prefixed bytes straight from the buffer, advancing `pos` only on success.
+    pub fn read_bytes_prefixed<P: LengthPrefix>(&mut self) -> Result<&[u8], DecodeError> {
+        let start = self.read_prefixed_start::<P>()?;
+        Ok(&self.filled()[start..self.pos()])
+    }
+
+    ///Borrows a length-prefixed UTF-8 string straight from the buffer, advancing `pos` only on success.
+    pub fn read_str_prefixed<P: LengthPrefix>(&mut self) -> Result<&str, DecodeError> {
+        let pos = self.pos();
+        let start = self.read_prefixed_start::<P>()?;
+        if core::str::from_utf8(&self.filled()[start..self.pos()]).is_err() {
+            unsafe { *self.pos_mut() = pos };
+            return Err(DecodeError::InvalidUtf8);
+        }
+        Ok(unsafe { core::str::from_utf8_unchecked(&self.filled()[start..self.pos()]) })
+    }
+
+    ///Skips the prefix and body, returning where the body starts.
+    fn read_prefixed_start<P: LengthPrefix>(&mut self) -> Result<usize, DecodeError> {
+        let pos = self.pos();
+        match P::read(self) {
+            Ok(len) if self.remaining() >= len => {
+                let start = self.pos();
+                unsafe { *self.pos_mut() = start + len };
+                Ok(start)
+            }
+            result => {
+                unsafe { *self.pos_mut() = pos };
+                Err(result.err().unwrap_or(DecodeError::Incomplete))
+            }
+        }
+    }
+
+    ///Copies a length-prefixed UTF-8 string into a [String], advancing `pos` only on success.
+    pub fn read_string_prefixed<P: LengthPrefix, const M: usize>(
+        &mut self,
+    ) -> Result<String<M>, DecodeError> {
+        let pos = self.pos();
+        let value = self.read_str_prefixed::<P>()?;
+        if value.len() > M {
+            unsafe { *self.pos_mut() = pos };
+            return Err(DecodeError::Capacity);
+        }
+        let mut string = String::new();
+        let vec = string.as_vec_mut();
+        vec.as_array_mut()[..value.len()].copy_from_slice(value.as_bytes());
+        unsafe { *vec.len_mut() = value.len() };
+        Ok(string)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DecodeError, EncodeError, PrefixU16, PrefixU8, PrefixVarInt};
+    use crate::{Cursor, LittleEndian, String};
+
+    #[test]
+    fn str_round_trip() {
+        let mut cursor: Cursor<u8, 64> = Cursor::new();
+        cursor.write_str_prefixed::<PrefixVarInt>("steve").unwrap();
+        cursor.write_str_prefixed::<PrefixU8>("chat").unwrap();
+        cursor
+            .write_str_prefixed::<PrefixU16<LittleEndian>>("alex")
+            .unwrap();
+        assert_eq!(&cursor.filled()[..6], b"\x05steve");
+        assert_eq!(cursor.read_str_prefixed::<PrefixVarInt>(), Ok("steve"));
+        let chat: String<8> = cursor.read_string_prefixed::<PrefixU8, 8>().unwrap();
+        assert_eq!(chat.as_str(), "chat");
+        assert_eq!(
+            cursor.read_bytes_prefixed::<PrefixU16<LittleEndian>>(),
+            Ok(&b"alex"[..])
+        );
+    }
+
+    #[test]
+    fn partial_and_invalid() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        cursor.push(4).unwrap();
+        cursor.push(b'a').unwrap();
+        assert_eq!(
+            cursor.read_str_prefixed::<PrefixU8>(),
+            Err(DecodeError::Incomplete)
+        );
+        assert_eq!(cursor.pos(), 0);
+        cursor.push(0xff).unwrap();
+        cursor.push(b'b').unwrap();
+        cursor.push(b'c').unwrap();
+        assert_eq!(
+            cursor.read_str_prefixed::<PrefixU8>(),
+            Err(DecodeError::InvalidUtf8)
+        );
+        assert_eq!(cursor.pos(), 0);
+        cursor.clear();
+        cursor.write_str_prefixed::<PrefixU8>("toolong").unwrap();
+        assert_eq!(
+            cursor.read_string_prefixed::<PrefixU8, 4>().map(|_| ()),
+            Err(DecodeError::Capacity)
+        );
+        assert_eq!(cursor.pos(), 0);
+    }
+
+    #[test]
+    fn write_errors() {
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        assert_eq!(
+            cursor.write_str_prefixed::<PrefixU8>("four"),
+            Err(EncodeError::BufferFull)
+        );
+        assert_eq!(cursor.filled_len(), 0);
+        let long = [0u8; 256];
+        let mut cursor: Cursor<u8, 300> = Cursor::new();
+        assert_eq!(
+            cursor.write_bytes_prefixed::<PrefixU8>(&long),
+            Err(EncodeError::LengthOverflow)
+        );
+    }
+}
@@ -3,12 +3,34 @@ use core::{
     slice::from_raw_parts,
 };
 
-use crate::const_transmute_unchecked;
+use crate::{const_transmute_unchecked, AsBytes, FromBytes, Pod};
 
 pub struct Cursor<T, const N: usize> {
     buffer: [MaybeUninit<T>; N],
     pos: usize,
     filled_len: usize,
+    ///Items before it were moved out by [Cursor::take] and are never dropped again.
+    taken: usize,
+    ///Items dropped from the front by [Cursor::clear] and [Cursor::compact], so a
+    ///[crate::Mark] keeps pointing at the same item.
+    discarded: usize,
+    ///Number of [Cursor::drain] calls that moved items out.
+    drains: usize,
+    ///Offset, counted like `discarded`, the latest of those drains started at.
+    drained_at: usize,
+}
+
+///Outcome of filling a cursor from a non-blocking reader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillStatus {
+    ///Number of bytes read.
+    Read(usize),
+    ///The reader reached end of stream, such as a closed peer.
+    Eof,
+    ///The cursor has no room left to read into.
+    BufferFull,
+    ///The reader has no bytes available yet, retry once it is readable.
+    WouldBlock,
 }
 
 impl<const N: usize> Cursor<u8, N> {
@@ -33,6 +55,39 @@ impl<const N: usize> Cursor<u8, N> {
     }
 }
 
+impl<const N: usize> Cursor<u8, N> {
+    ///Reads a copy of `V` at `pos` regardless of its alignment.
+    #[inline(always)]
+    pub fn read_value<V: FromBytes>(&mut self) -> Option<V> {
+        let value = self.peek_value()?;
+        self.pos += size_of::<V>();
+        Some(value)
+    }
+
+    ///Same as [Self::read_value] without advancing `pos`.
+    #[inline(always)]
+    pub fn peek_value<V: FromBytes>(&self) -> Option<V> {
+        self.get_value(self.pos)
+    }
+
+    ///Reads a copy of `V` at `index` of the filled region regardless of its alignment.
+    #[inline(always)]
+    pub fn get_value<V: FromBytes>(&self, index: usize) -> Option<V> {
+        if index + size_of::<V>() <= self.filled_len {
+            Some(unsafe { self.get_value_unchecked(index) })
+        } else {
+            None
+        }
+    }
+
+    ///# Safety
+    ///`index + size_of::<V>()` must not exceed [Self::filled_len].
+    #[inline(always)]
+    pub unsafe fn get_value_unchecked<V: FromBytes>(&self, index: usize) -> V {
+        self.buffer.as_ptr().add(index).cast::<V>().read_unaligned()
+    }
+}
+
 impl<T, const N: usize> Cursor<T, N> {
     #[inline(always)]
     pub const fn new() -> Self {
@@ -40,13 +95,32 @@ impl<T, const N: usize> Cursor<T, N> {
             buffer: [const { MaybeUninit::uninit() }; N],
             pos: 0,
             filled_len: 0,
+            taken: 0,
+            discarded: 0,
+            drains: 0,
+            drained_at: 0,
         }
     }
 
+    ///Drops every filled item and resets the positions.
     #[inline(always)]
     pub fn clear(&mut self) {
+        let (taken, filled_len) = (self.taken, self.filled_len);
+        self.discarded = self.discarded.wrapping_add(filled_len);
         self.filled_len = 0;
         self.pos = 0;
+        self.taken = 0;
+        unsafe { self.drop_items(taken, filled_len) };
+    }
+
+    ///# Safety
+    ///Items in `start..end` must be initialized and no longer reachable through the cursor.
+    #[inline(always)]
+    unsafe fn drop_items(&mut self, start: usize, end: usize) {
+        core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+            (self.buffer.as_mut_ptr() as *mut T).add(start),
+            end - start,
+        ));
     }
 
     #[inline(always)]
@@ -97,6 +171,56 @@ impl<T, const N: usize> Cursor<T, N> {
         self.buffer.get_unchecked(pos).assume_init_ref()
     }
 
+    ///Moves the item at [Self::pos] out and advances past it, dropping the items
+    ///[Self::read] already went over. [Self::compact] reclaims the vacated slots.
+    pub fn take(&mut self) -> Option<T> {
+        let pos = self.pos;
+        if pos == self.filled_len {
+            return None;
+        }
+        let taken = self.taken;
+        let item = unsafe { self.buffer.get_unchecked(pos).assume_init_read() };
+        self.pos = pos + 1;
+        self.taken = pos + 1;
+        unsafe { self.drop_items(taken, pos) };
+        Some(item)
+    }
+
+    ///Number of leading items moved out by [Self::take].
+    #[inline(always)]
+    pub fn taken_len(&self) -> usize {
+        self.taken
+    }
+
+    #[inline(always)]
+    pub(crate) fn discarded_len(&self) -> usize {
+        self.discarded
+    }
+
+    ///Number of draining [Self::drain] calls and the offset the latest one started at.
+    #[inline(always)]
+    pub(crate) fn latest_drain(&self) -> (usize, usize) {
+        (self.drains, self.drained_at)
+    }
+
+    ///Moves the unread items out in order, leaving the read ones in place.
+    ///Items the iterator does not yield are dropped with it.
+    ///Marks taken past `pos` are invalidated, as the items pushed next reuse their offsets.
+    pub fn drain(&mut self) -> Drain<'_, T, N> {
+        let start = self.pos;
+        let end = self.filled_len;
+        self.filled_len = start;
+        if start != end {
+            self.drains = self.drains.wrapping_add(1);
+            self.drained_at = self.discarded.wrapping_add(start);
+        }
+        Drain {
+            buffer: &mut self.buffer,
+            start,
+            end,
+        }
+    }
+
     #[inline(always)]
     pub const fn pos(&self) -> usize {
         self.pos
@@ -107,6 +231,30 @@ impl<T, const N: usize> Cursor<T, N> {
         &mut self.pos
     }
 
+    ///Items from [Self::pos] to [Self::filled_len].
+    #[inline(always)]
+    pub fn unread(&self) -> &[T] {
+        unsafe {
+            from_raw_parts(
+                (self.buffer.as_ptr() as *const T).add(self.pos),
+                self.filled_len - self.pos,
+            )
+        }
+    }
+
+    ///Unread items along with `pos`, borrowed from disjoint fields.
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub(crate) fn unread_and_pos_mut(&mut self) -> (&[T], &mut usize) {
+        let unread = unsafe {
+            from_raw_parts(
+                (self.buffer.as_ptr() as *const T).add(self.pos),
+                self.filled_len - self.pos,
+            )
+        };
+        (unread, &mut self.pos)
+    }
+
     #[inline(always)]
     pub fn filled_len(&self) -> usize {
         self.filled_len
@@ -117,11 +265,8 @@ impl<T, const N: usize> Cursor<T, N> {
         &mut self.filled_len
     }
 
-    #[inline(always)]
-    pub fn filled(&self) -> &[T] {
-        unsafe { from_raw_parts(self.buffer.as_ptr() as *const T, self.filled_len) }
-    }
-
+    ///# Safety
+    ///The first [Self::taken_len] items were moved out and must not be used.
     #[inline(always)]
     pub unsafe fn filled_mut(&mut self) -> &mut [T] {
         core::slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut T, self.filled_len)
@@ -145,9 +290,56 @@ impl<T, const N: usize> Cursor<T, N> {
         )
     }
 
+    ///Moves the unread items to the front of the buffer, dropping the consumed prefix.
+    pub fn compact(&mut self) {
+        let pos = self.pos;
+        if pos != 0 {
+            let remaining = self.remaining();
+            let taken = self.taken;
+            self.taken = pos;
+            unsafe {
+                self.drop_items(taken, pos);
+                let ptr = self.buffer.as_mut_ptr();
+                core::ptr::copy(ptr.add(pos), ptr, remaining);
+            }
+            self.pos = 0;
+            self.taken = 0;
+            self.discarded = self.discarded.wrapping_add(pos);
+            self.filled_len = remaining;
+        }
+    }
+
+    ///Ensures at least `len` contiguous unfilled items, compacting only when the tail is too small.
+    pub fn reserve_contiguous(&mut self, len: usize) -> Result<(), ()> {
+        if N - self.filled_len >= len {
+            Ok(())
+        } else if N - self.remaining() >= len {
+            self.compact();
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        self.buffer.get_unchecked(index).assume_init_ref()
+    }
+
+    #[inline(always)]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        self.buffer.get_unchecked_mut(index).assume_init_mut()
+    }
+}
+
+///Transmute APIs view `V` as a whole number of items starting at item `index`,
+///so `size_of::<V>()` must be a multiple of `size_of::<T>()`.
+///Those returning references also need `V` to be no more aligned than `T`.
+impl<T: Pod, const N: usize> Cursor<T, N> {
+    #[deprecated(note = "unaligned references are undefined behavior, use `Cursor::get_value`")]
     #[inline(always)]
-    pub fn get_transmute<V>(&self, index: usize) -> Option<&V> {
-        if index < N as usize {
+    pub fn get_transmute<V: FromBytes>(&self, index: usize) -> Option<&V> {
+        if self.in_filled::<V>(index) {
             Some(unsafe { self.get_transmute_unchecked(index) })
         } else {
             None
@@ -155,46 +347,49 @@ impl<T, const N: usize> Cursor<T, N> {
     }
 
     #[inline(always)]
-    pub fn get_transmute_mut<V>(&mut self, index: usize) -> Option<&mut V> {
-        if index < N as usize {
+    pub fn get_transmute_mut<V: Pod>(&mut self, index: usize) -> Option<&mut V> {
+        if self.in_filled::<V>(index) {
             Some(unsafe { self.get_transmute_mut_unchecked(index) })
         } else {
             None
         }
     }
 
+    ///# Safety
+    ///`index..index + size_of::<V>() / size_of::<T>()` must be within the filled items.
     #[inline(always)]
-    pub unsafe fn get_transmute_unchecked<V>(&self, index: usize) -> &V {
-        let value = (&self.buffer) as *const _ as *const u8;
-        &*value.offset(index as isize).cast::<V>()
+    pub unsafe fn get_transmute_unchecked<V: FromBytes>(&self, index: usize) -> &V {
+        const { transmute_ref_len::<T, V>() };
+        &*(self.buffer.as_ptr().add(index) as *const V)
     }
 
+    ///# Safety
+    ///Same as [Self::get_transmute_unchecked].
     #[inline(always)]
-    pub unsafe fn get_transmute_mut_unchecked<V>(&mut self, index: usize) -> &mut V {
-        let value = (&mut self.buffer) as *mut _ as *mut u8;
-        &mut *value.offset(index as isize).cast::<V>()
+    pub unsafe fn get_transmute_mut_unchecked<V: Pod>(&mut self, index: usize) -> &mut V {
+        const { transmute_ref_len::<T, V>() };
+        &mut *(self.buffer.as_mut_ptr().add(index) as *mut V)
     }
 
-    pub fn push_transmute<V>(&mut self, value: V) -> Result<(), ()> {
-        if size_of::<V>() + self.filled_len < N {
+    pub fn push_transmute<V: AsBytes>(&mut self, value: V) -> Result<(), ()> {
+        if const { transmute_len::<T, V>() } <= N - self.filled_len {
             Ok(unsafe { self.push_transmute_unchecked(value) })
         } else {
             Err(())
         }
     }
 
-    pub unsafe fn push_transmute_unchecked<V>(&mut self, value: V) {
-        const { assert_types::<T, V>() };
-        let ptr = (&mut self.buffer) as *mut _ as *mut u8;
-        *ptr.offset(self.filled_len as isize).cast::<V>() = value;
-        *self.filled_len_mut() = self
-            .filled_len
-            .unchecked_add(const { calc_index_from_input_size_and_unit_isze::<T, V>() });
+    pub unsafe fn push_transmute_unchecked<V: AsBytes>(&mut self, value: V) {
+        let len = const { transmute_len::<T, V>() };
+        let ptr = self.buffer.as_mut_ptr().add(self.filled_len);
+        ptr.cast::<V>().write_unaligned(value);
+        *self.filled_len_mut() = self.filled_len.unchecked_add(len);
     }
 
+    #[deprecated(note = "unaligned references are undefined behavior, use `Cursor::read_value`")]
     #[inline(always)]
-    pub fn read_transmute<V>(&mut self) -> Option<&V> {
-        if self.pos() + core::mem::size_of::<V>() > self.filled_len() {
+    pub fn read_transmute<V: FromBytes>(&mut self) -> Option<&V> {
+        if const { transmute_len::<T, V>() } > self.remaining() {
             None
         } else {
             Some(unsafe { self.read_transmute_unchecked() })
@@ -202,35 +397,32 @@ impl<T, const N: usize> Cursor<T, N> {
     }
 
     #[inline(always)]
-    pub unsafe fn read_transmute_unchecked<V>(&mut self) -> &V {
-        let value = (&mut self.buffer) as *const _ as *const u8;
-        let result = &*value.offset(self.pos as isize).cast::<V>();
-        *self.pos_mut() = self.pos.unchecked_add(core::mem::size_of::<V>());
-        result
-    }
-
-    #[inline(always)]
-    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
-        self.buffer.get_unchecked(index).assume_init_ref()
+    pub unsafe fn read_transmute_unchecked<V: FromBytes>(&mut self) -> &V {
+        let len = const { transmute_ref_len::<T, V>() };
+        let pos = self.pos;
+        *self.pos_mut() = pos.unchecked_add(len);
+        &*(self.buffer.as_ptr().add(pos) as *const V)
     }
 
     #[inline(always)]
-    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
-        self.buffer.get_unchecked_mut(index).assume_init_mut()
+    fn in_filled<V>(&self, index: usize) -> bool {
+        index
+            .checked_add(const { transmute_len::<T, V>() })
+            .is_some_and(|end| end <= self.filled_len)
     }
 
-    pub fn set_transmute<V>(&mut self, index: usize, value: V) -> Result<(), ()> {
-        if index + core::mem::size_of::<V>() < N {
+    pub fn set_transmute<V: AsBytes>(&mut self, index: usize, value: V) -> Result<(), ()> {
+        if index <= N && const { transmute_len::<T, V>() } <= N - index {
             unsafe { Ok(self.set_transmute_unchecked(index, value)) }
         } else {
             Err(())
         }
     }
 
-    pub unsafe fn set_transmute_unchecked<V>(&mut self, index: usize, value: V) {
-        const { assert_types::<T, V>() };
-        let ptr = (&mut self.buffer) as *mut _ as *mut u8;
-        *ptr.offset(index as isize).cast::<V>() = value;
+    pub unsafe fn set_transmute_unchecked<V: AsBytes>(&mut self, index: usize, value: V) {
+        const { transmute_len::<T, V>() };
+        let ptr = self.buffer.as_mut_ptr().add(index);
+        ptr.cast::<V>().write_unaligned(value);
     }
 }
 
@@ -243,8 +435,31 @@ impl<const N: usize> std::io::Read for Cursor<u8, N> {
 
 #[cfg(feature = "std")]
 impl<const N: usize> std::io::Write for Cursor<u8, N> {
-    fn write(&mut self, mut buf: &[u8]) -> std::io::Result<usize> {
-        self.push_from_read(&mut buf)
+    ///Copies as much of `buf` as fits. Returns `Ok(0)` once the cursor is full,
+    ///which [std::io::Write::write_all] reports as [std::io::ErrorKind::WriteZero].
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let unfilled = unsafe { self.unfilled_mut() };
+        let len = crate::min(unfilled.len(), buf.len());
+        unfilled[..len].copy_from_slice(&buf[..len]);
+        self.filled_len += len;
+        Ok(len)
+    }
+
+    ///Copies each buffer into the unfilled region until the cursor is full, like [Self::write].
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            let len = self.write(buf)?;
+            written += len;
+            if len < buf.len() {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
@@ -252,8 +467,14 @@ impl<const N: usize> std::io::Write for Cursor<u8, N> {
     }
 }
 
+///Most cursors gathered or scattered by a single vectored call, longer lists are split.
+#[cfg(feature = "std")]
+const MAX_VECTORED_CURSORS: usize = 64;
+
 #[cfg(feature = "std")]
 impl<const N: usize> Cursor<u8, N> {
+    ///Fails with [std::io::ErrorKind::ConnectionAborted] on a 0-byte read, including when the
+    ///cursor is full. Use [Self::fill_from_read] to tell those cases apart.
     pub fn push_from_read<R: std::io::Read>(&mut self, read: &mut R) -> std::io::Result<usize> {
         let unfilled = unsafe { self.unfilled_mut() };
         let read_length = read.read(unfilled)?;
@@ -268,24 +489,391 @@ impl<const N: usize> Cursor<u8, N> {
         Ok(read_length)
     }
 
+    ///Reads once into the unfilled tail, reporting end of stream, a full buffer and
+    ///[std::io::ErrorKind::WouldBlock] as a [FillStatus] instead of an error.
+    ///Interrupted reads are retried.
+    pub fn fill_from_read<R: std::io::Read>(
+        &mut self,
+        read: &mut R,
+    ) -> std::io::Result<FillStatus> {
+        use std::io::ErrorKind;
+        if self.filled_len == N {
+            return Ok(FillStatus::BufferFull);
+        }
+        loop {
+            let unfilled = unsafe { self.unfilled_mut() };
+            return match read.read(unfilled) {
+                Ok(0) => Ok(FillStatus::Eof),
+                Ok(read_length) => {
+                    self.filled_len += read_length;
+                    Ok(FillStatus::Read(read_length))
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => Ok(FillStatus::WouldBlock),
+                Err(err) => Err(err),
+            };
+        }
+    }
+
+    ///Reads until at least `min_bytes` are unread, compacting when the tail is too small.
+    ///Returns [FillStatus::Read] with the total bytes read once satisfied, otherwise the
+    ///status that stopped it while keeping whatever was read.
+    ///[FillStatus::BufferFull] is returned right away when `min_bytes` exceeds the capacity.
+    pub fn fill_to<R: std::io::Read>(
+        &mut self,
+        read: &mut R,
+        min_bytes: usize,
+    ) -> std::io::Result<FillStatus> {
+        if min_bytes > N {
+            return Ok(FillStatus::BufferFull);
+        }
+        let mut total = 0;
+        while self.remaining() < min_bytes {
+            if N - self.pos < min_bytes {
+                self.compact();
+            }
+            match self.fill_from_read(read)? {
+                FillStatus::Read(read_length) => total += read_length,
+                status => return Ok(status),
+            }
+        }
+        Ok(FillStatus::Read(total))
+    }
+
+    ///Fills until `buf.len()` bytes are unread, then copies them into `buf` and advances `pos`.
+    ///Nothing is consumed unless the whole of `buf` could be filled.
+    pub fn read_exact_from<R: std::io::Read>(
+        &mut self,
+        read: &mut R,
+        buf: &mut [u8],
+    ) -> std::io::Result<FillStatus> {
+        let status = self.fill_to(read, buf.len())?;
+        if let FillStatus::Read(_) = status {
+            let pos = self.pos;
+            buf.copy_from_slice(&self.filled()[pos..pos + buf.len()]);
+            self.pos = pos + buf.len();
+        }
+        Ok(status)
+    }
+
+    ///Same as [Self::push_from_read] but compacts first when the unfilled tail is empty.
+    pub fn push_from_read_compact<R: std::io::Read>(
+        &mut self,
+        read: &mut R,
+    ) -> std::io::Result<usize> {
+        if self.filled_len == N {
+            self.compact();
+        }
+        self.push_from_read(read)
+    }
+
+    ///Writes the unread bytes once, advancing `pos` by the written length.
+    ///The cursor is cleared only when every byte has been written.
     pub fn push_to_write<W: std::io::Write>(&mut self, write: &mut W) -> std::io::Result<usize> {
-        let write_len = write.write(self.filled())?;
-        self.clear();
+        let pos = self.pos();
+        let write_len = write.write(&self.filled()[pos..])?;
+        self.consume_written(write_len);
         Ok(write_len)
     }
+
+    ///Writes unread bytes until they are exhausted or the writer would block,
+    ///returning the number of bytes still remaining in the cursor.
+    pub fn push_all_to_write<W: std::io::Write>(
+        &mut self,
+        write: &mut W,
+    ) -> std::io::Result<usize> {
+        use std::io::{Error, ErrorKind};
+        while self.remaining() != 0 {
+            match self.push_to_write(write) {
+                Ok(0) => Err(Error::new(ErrorKind::WriteZero, "write length was 0"))?,
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) => Err(err)?,
+            }
+        }
+        Ok(self.remaining())
+    }
+
+    ///Gathers the unread bytes of `cursors` into vectored writes of up to 64 cursors each,
+    ///consuming the written length from the cursors in order.
+    ///Moves on to the next 64 cursors only once the previous ones were fully written,
+    ///and reports an error only when nothing was written.
+    pub fn push_to_write_vectored<W: std::io::Write>(
+        cursors: &mut [&mut Self],
+        write: &mut W,
+    ) -> std::io::Result<usize> {
+        use std::io::IoSlice;
+        let mut total = 0;
+        for chunk in cursors.chunks_mut(MAX_VECTORED_CURSORS) {
+            let mut slices = [IoSlice::new(&[]); MAX_VECTORED_CURSORS];
+            let mut chunk_len = 0;
+            for (slice, cursor) in slices.iter_mut().zip(chunk.iter()) {
+                *slice = IoSlice::new(cursor.unread());
+                chunk_len += cursor.remaining();
+            }
+            let write_len = match write.write_vectored(&slices[..chunk.len()]) {
+                Ok(write_len) => write_len,
+                Err(_) if total != 0 => break,
+                Err(err) => return Err(err),
+            };
+            let mut left = write_len;
+            for cursor in chunk.iter_mut() {
+                let len = crate::min(left, cursor.remaining());
+                cursor.consume_written(len);
+                left -= len;
+            }
+            total += write_len;
+            if write_len < chunk_len {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    ///Scatters vectored reads into the unfilled regions of `cursors` in order, up to 64 cursors
+    ///per read, moving on to the next 64 cursors only once the previous ones were filled.
+    ///Reports [FillStatus::Read] with the total once anything was read, like [Self::fill_from_read].
+    pub fn push_from_read_vectored<R: std::io::Read>(
+        cursors: &mut [&mut Self],
+        read: &mut R,
+    ) -> std::io::Result<FillStatus> {
+        use std::io::{ErrorKind, IoSliceMut};
+        let mut total = 0;
+        let mut status = FillStatus::BufferFull;
+        for chunk in cursors.chunks_mut(MAX_VECTORED_CURSORS) {
+            let chunk_len: usize = chunk.iter().map(|cursor| N - cursor.filled_len).sum();
+            if chunk_len == 0 {
+                continue;
+            }
+            let count = chunk.len();
+            let result = loop {
+                let mut unfilled = chunk.iter_mut();
+                let mut slices: [IoSliceMut; MAX_VECTORED_CURSORS] = core::array::from_fn(|_| {
+                    IoSliceMut::new(match unfilled.next() {
+                        Some(cursor) => unsafe { cursor.unfilled_mut() },
+                        None => &mut [],
+                    })
+                });
+                match read.read_vectored(&mut slices[..count]) {
+                    Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                    result => break result,
+                }
+            };
+            let read_length = match result {
+                Ok(0) => {
+                    status = FillStatus::Eof;
+                    break;
+                }
+                Ok(read_length) => read_length,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    status = FillStatus::WouldBlock;
+                    break;
+                }
+                Err(_) if total != 0 => break,
+                Err(err) => return Err(err),
+            };
+            let mut left = read_length;
+            for cursor in chunk.iter_mut() {
+                let len = crate::min(left, N - cursor.filled_len);
+                cursor.filled_len += len;
+                left -= len;
+            }
+            total += read_length;
+            if read_length < chunk_len {
+                break;
+            }
+        }
+        if total != 0 {
+            Ok(FillStatus::Read(total))
+        } else {
+            Ok(status)
+        }
+    }
+
+    #[inline(always)]
+    fn consume_written(&mut self, write_len: usize) {
+        let pos = self.pos + write_len;
+        if pos < self.filled_len {
+            self.pos = pos;
+        } else {
+            self.clear();
+        }
+    }
 }
 
-const fn assert_types<T, V>() {
-    assert!(core::mem::size_of::<V>() >= core::mem::size_of::<T>())
+#[cfg(feature = "bytes")]
+impl<const N: usize> bytes::Buf for Cursor<u8, N> {
+    #[inline(always)]
+    fn remaining(&self) -> usize {
+        Cursor::remaining(self)
+    }
+
+    #[inline(always)]
+    fn chunk(&self) -> &[u8] {
+        &self.filled()[self.pos..]
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= Cursor::remaining(self),
+            "cannot advance past the filled region"
+        );
+        self.pos += cnt;
+    }
 }
 
-const fn calc_index_from_input_size_and_unit_isze<T, V>() -> usize {
-    let input_size = core::mem::size_of::<V>();
-    let unit_size = core::mem::size_of::<T>();
-    if input_size % unit_size != 0 {
-        input_size / unit_size + 1
-    } else {
-        input_size / unit_size
+#[cfg(feature = "bytes")]
+unsafe impl<const N: usize> bytes::BufMut for Cursor<u8, N> {
+    #[inline(always)]
+    fn remaining_mut(&self) -> usize {
+        N - self.filled_len
+    }
+
+    #[inline(always)]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= N - self.filled_len,
+            "cannot advance past the capacity"
+        );
+        self.filled_len += cnt;
+    }
+
+    #[inline(always)]
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        let filled_len = self.filled_len;
+        bytes::buf::UninitSlice::uninit(&mut self.buffer[filled_len..])
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<const N: usize> tokio::io::AsyncRead for Cursor<u8, N> {
+    ///Copies unread bytes out, advancing `pos`.
+    fn poll_read(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> core::task::Poll<std::io::Result<()>> {
+        let cursor = self.get_mut();
+        let len = crate::min(cursor.remaining(), buf.remaining());
+        let pos = cursor.pos;
+        buf.put_slice(&cursor.filled()[pos..pos + len]);
+        cursor.pos = pos + len;
+        core::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<const N: usize> tokio::io::AsyncWrite for Cursor<u8, N> {
+    ///Copies as many bytes as fit into the unfilled region.
+    fn poll_write(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+        buf: &[u8],
+    ) -> core::task::Poll<std::io::Result<usize>> {
+        let cursor = self.get_mut();
+        let unfilled = unsafe { cursor.unfilled_mut() };
+        let len = crate::min(unfilled.len(), buf.len());
+        unfilled[..len].copy_from_slice(&buf[..len]);
+        cursor.filled_len += len;
+        core::task::Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<std::io::Result<()>> {
+        core::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<std::io::Result<()>> {
+        core::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<const N: usize> Cursor<u8, N> {
+    ///Async counterpart of [Self::push_from_read].
+    pub async fn push_from_async_read<R: tokio::io::AsyncRead + Unpin>(
+        &mut self,
+        read: &mut R,
+    ) -> std::io::Result<usize> {
+        let filled_len = self.filled_len;
+        let mut buf = tokio::io::ReadBuf::uninit(&mut self.buffer[filled_len..]);
+        core::future::poll_fn(|cx| core::pin::Pin::new(&mut *read).poll_read(cx, &mut buf)).await?;
+        let read_length = buf.filled().len();
+        if read_length == 0 {
+            use std::io::{Error, ErrorKind};
+            Err(Error::new(
+                ErrorKind::ConnectionAborted,
+                "read length was 0",
+            ))?;
+        }
+        self.filled_len = filled_len + read_length;
+        Ok(read_length)
+    }
+
+    ///Async counterpart of [Self::push_to_write], keeping the bytes that were not written.
+    pub async fn push_to_async_write<W: tokio::io::AsyncWrite + Unpin>(
+        &mut self,
+        write: &mut W,
+    ) -> std::io::Result<usize> {
+        let pos = self.pos;
+        let unread = &self.filled()[pos..];
+        let write_len =
+            core::future::poll_fn(|cx| core::pin::Pin::new(&mut *write).poll_write(cx, unread))
+                .await?;
+        self.consume_written(write_len);
+        Ok(write_len)
+    }
+
+    ///Async counterpart of [Self::push_all_to_write], writing until no unread bytes remain.
+    pub async fn push_all_to_async_write<W: tokio::io::AsyncWrite + Unpin>(
+        &mut self,
+        write: &mut W,
+    ) -> std::io::Result<()> {
+        use std::io::{Error, ErrorKind};
+        while self.remaining() != 0 {
+            match self.push_to_async_write(write).await {
+                Ok(0) => Err(Error::new(ErrorKind::WriteZero, "write length was 0"))?,
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => Err(err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+///Number of `T` items a `V` spans.
+///[transmute_len] for values viewed in place by reference.
+const fn transmute_ref_len<T, V>() -> usize {
+    assert!(
+        core::mem::align_of::<V>() <= core::mem::align_of::<T>(),
+        "referenced values must not be more aligned than the items"
+    );
+    transmute_len::<T, V>()
+}
+
+const fn transmute_len<T, V>() -> usize {
+    assert!(
+        size_of::<T>() != 0 && size_of::<V>() % size_of::<T>() == 0,
+        "transmuted values must span a whole number of items"
+    );
+    size_of::<V>() / size_of::<T>()
+}
+
+impl<T: Copy, const N: usize> Cursor<T, N> {
+    ///Every filled item, including those before [Self::pos].
+    ///Items moved out by [Self::take] are still visible here, so this needs `Copy` items,
+    ///use [Self::unread] otherwise.
+    #[inline(always)]
+    pub fn filled(&self) -> &[T] {
+        unsafe { from_raw_parts(self.buffer.as_ptr() as *const T, self.filled_len) }
     }
 }
 
@@ -295,6 +883,10 @@ impl<T: Copy, const N: usize> Clone for Cursor<T, N> {
             buffer: [MaybeUninit::uninit(); N],
             pos: self.pos.clone(),
             filled_len: self.filled_len.clone(),
+            taken: self.taken,
+            discarded: self.discarded,
+            drains: self.drains,
+            drained_at: self.drained_at,
         };
         cursor.buffer.copy_from_slice(&self.buffer.as_slice());
         cursor
@@ -308,8 +900,97 @@ impl<T, const N: usize> Default for Cursor<T, N> {
             buffer: [const { MaybeUninit::uninit() }; N],
             pos: Default::default(),
             filled_len: Default::default(),
+            taken: Default::default(),
+            discarded: Default::default(),
+            drains: Default::default(),
+            drained_at: Default::default(),
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Cursor<T, N> {
+    fn drop(&mut self) {
+        unsafe { self.drop_items(self.taken, self.filled_len) }
+    }
+}
+
+///Iterator returned by [Cursor::drain].
+pub struct Drain<'a, T, const N: usize> {
+    buffer: &'a mut [MaybeUninit<T>; N],
+    start: usize,
+    end: usize,
+}
+
+impl<T, const N: usize> Iterator for Drain<'_, T, N> {
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<T> {
+        if self.start < self.end {
+            let item = unsafe { self.buffer.get_unchecked(self.start).assume_init_read() };
+            self.start += 1;
+            Some(item)
+        } else {
+            None
         }
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Drain<'_, T, N> {}
+
+impl<T, const N: usize> Drop for Drain<'_, T, N> {
+    fn drop(&mut self) {
+        for item in &mut self.buffer[self.start..self.end] {
+            unsafe { item.assume_init_drop() };
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for Cursor<T, N> {
+    ///Serialized as a sequence of the unread items, so a round trip keeps what is left to read
+    ///and starts over at `pos` 0.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.unread())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for Cursor<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CursorVisitor<T, const N: usize>(core::marker::PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::de::Visitor<'de>
+            for CursorVisitor<T, N>
+        {
+            type Value = Cursor<T, N>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a sequence of at most {N} elements")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut cursor = Cursor::new();
+                while let Some(value) = seq.next_element()? {
+                    if cursor.push(value).is_err() {
+                        return Err(serde::de::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Ok(cursor)
+            }
+        }
+
+        deserializer.deserialize_seq(CursorVisitor(core::marker::PhantomData))
+    }
 }
 
 #[cfg(test)]
@@ -317,16 +998,22 @@ mod test {
     use crate::const_transmute_unchecked;
 
     use super::Cursor;
+    #[cfg(feature = "std")]
+    use super::FillStatus;
     use rand::Rng;
 
     #[test]
+    #[allow(deprecated)]
     fn test() {
         let mut buffer: Cursor<u8, 100> = Cursor::new();
         for i in 1..5 {
             buffer.push(i).unwrap();
         }
         assert_eq!(buffer.filled_len(), 4);
-        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        #[derive(
+            Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, fast_collections_derive::Pod,
+        )]
+        #[repr(C)]
         struct A {
             v0: u8,
             v1: u8,
@@ -338,6 +1025,7 @@ mod test {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn filled() {
         let mut buffer: Cursor<u8, 8> = Cursor::new();
         for i in 1..9 {
@@ -361,7 +1049,7 @@ mod test {
         let _value: [u8; 2] = unsafe { const_transmute_unchecked(100u16) };
         cursor.push_transmute(260u16).unwrap();
         assert_eq!(cursor.filled_len(), 2);
-        assert_eq!(cursor.read_transmute::<u16>().unwrap(), &260u16);
+        assert_eq!(cursor.read_value::<u16>().unwrap(), 260u16);
     }
 
     #[test]
@@ -371,6 +1059,385 @@ mod test {
         let value: usize = rand::thread_rng().gen();
         src.push_transmute(value).unwrap();
         dst.push_from_cursor(&mut src).unwrap();
-        assert_eq!(dst.read_transmute::<usize>().unwrap(), &value);
+        assert_eq!(dst.read_value::<usize>().unwrap(), value);
+    }
+
+    #[test]
+    fn compact() {
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        for i in 1..9 {
+            cursor.push(i).unwrap();
+        }
+        cursor.read_value::<[u8; 5]>().unwrap();
+        assert!(cursor.push(9).is_err());
+        cursor.compact();
+        assert_eq!(cursor.pos(), 0);
+        assert_eq!(cursor.filled(), &[6, 7, 8]);
+        cursor.push(9).unwrap();
+        assert_eq!(cursor.filled(), &[6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn reserve_contiguous() {
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        for i in 1..7 {
+            cursor.push(i).unwrap();
+        }
+        cursor.reserve_contiguous(2).unwrap();
+        assert_eq!(cursor.pos(), 0);
+        cursor.read_value::<[u8; 3]>().unwrap();
+        assert!(cursor.reserve_contiguous(6).is_err());
+        cursor.reserve_contiguous(5).unwrap();
+        assert_eq!(cursor.filled(), &[4, 5, 6]);
+        assert_eq!(cursor.unfilled().len(), 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn push_from_read_compact() {
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        cursor.push_from_read(&mut &[1u8, 2, 3, 4][..]).unwrap();
+        cursor.read_value::<[u8; 2]>().unwrap();
+        assert_eq!(
+            cursor
+                .push_from_read_compact(&mut &[5u8, 6, 7][..])
+                .unwrap(),
+            2
+        );
+        assert_eq!(cursor.filled(), &[3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn read_value() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        cursor.push(0).unwrap();
+        cursor.push_transmute(0x0102_0304u32).unwrap();
+        assert_eq!(cursor.read_value::<u8>(), Some(0));
+        assert_eq!(cursor.peek_value::<u32>(), Some(0x0102_0304));
+        assert_eq!(cursor.get_value::<u32>(1), Some(0x0102_0304));
+        assert_eq!(cursor.get_value::<u32>(2), None);
+        assert_eq!(cursor.read_value::<u32>(), Some(0x0102_0304));
+        assert_eq!(cursor.read_value::<u8>(), None);
+    }
+
+    struct ShortWriter {
+        written: std::vec::Vec<u8>,
+        limit: usize,
+        calls: usize,
+    }
+
+    impl std::io::Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.calls == 2 {
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+            self.calls += 1;
+            let len = buf.len().min(self.limit);
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn push_to_write_keeps_unwritten_bytes() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        for i in 0..10 {
+            cursor.push(i).unwrap();
+        }
+        let mut writer = ShortWriter {
+            written: std::vec::Vec::new(),
+            limit: 4,
+            calls: 0,
+        };
+        assert_eq!(cursor.push_to_write(&mut writer).unwrap(), 4);
+        assert_eq!(cursor.pos(), 4);
+        assert_eq!(cursor.remaining(), 6);
+        assert_eq!(cursor.push_all_to_write(&mut writer).unwrap(), 2);
+        assert_eq!(writer.written, [0, 1, 2, 3, 4, 5, 6, 7]);
+        writer.calls = 0;
+        assert_eq!(cursor.push_all_to_write(&mut writer).unwrap(), 0);
+        assert_eq!(writer.written, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(cursor.filled_len(), 0);
+        assert_eq!(cursor.pos(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde::{de::value::SeqDeserializer, Deserialize};
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+        let deserializer = SeqDeserializer::<_, serde::de::value::Error>::new([1u8, 2].into_iter());
+        let mut cursor = Cursor::<u8, 4>::deserialize(deserializer).unwrap();
+        assert_eq!(cursor.filled(), &[1, 2]);
+        cursor.read();
+        assert_ser_tokens(
+            &cursor,
+            &[Token::Seq { len: Some(1) }, Token::U8(2), Token::SeqEnd],
+        );
+        assert_de_tokens_error::<Cursor<u8, 1>>(
+            &[Token::Seq { len: Some(2) }, Token::U8(1), Token::U8(2)],
+            "invalid length 2, expected a sequence of at most 1 elements",
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_read_write() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let (mut client, mut server) = tokio::io::duplex(4);
+        let mut outgoing: Cursor<u8, 16> = Cursor::new();
+        for i in 0..10 {
+            outgoing.push(i).unwrap();
+        }
+        assert_eq!(outgoing.push_to_async_write(&mut client).await.unwrap(), 4);
+        assert_eq!(outgoing.remaining(), 6);
+        let mut incoming: Cursor<u8, 16> = Cursor::new();
+        assert_eq!(incoming.push_from_async_read(&mut server).await.unwrap(), 4);
+        let writer = tokio::spawn(async move {
+            outgoing.push_all_to_async_write(&mut client).await.unwrap();
+            assert_eq!(outgoing.filled_len(), 0);
+        });
+        while incoming.filled_len() < 10 {
+            incoming.push_from_async_read(&mut server).await.unwrap();
+        }
+        writer.await.unwrap();
+        assert_eq!(incoming.filled(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let mut buf = [0u8; 3];
+        incoming.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [0, 1, 2]);
+        assert_eq!(incoming.pos(), 3);
+        incoming.write_all(&[10, 11]).await.unwrap();
+        assert_eq!(incoming.remaining(), 9);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn fill_status() {
+        struct Chunks<'a>(&'a [&'a [u8]]);
+        impl std::io::Read for Chunks<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                use std::io::{Error, ErrorKind};
+                let Some((chunk, rest)) = self.0.split_first() else {
+                    return Ok(0);
+                };
+                self.0 = rest;
+                if chunk.is_empty() {
+                    return Err(Error::from(ErrorKind::WouldBlock));
+                }
+                buf[..chunk.len()].copy_from_slice(chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let mut cursor: Cursor<u8, 6> = Cursor::new();
+        let mut read = Chunks(&[&[1, 2], &[], &[3, 4, 5], &[6, 7]]);
+        assert_eq!(
+            cursor.fill_to(&mut read, 4).unwrap(),
+            FillStatus::WouldBlock
+        );
+        assert_eq!(cursor.filled(), &[1, 2]);
+        let mut buf = [0; 4];
+        assert_eq!(
+            cursor.read_exact_from(&mut read, &mut buf).unwrap(),
+            FillStatus::Read(3)
+        );
+        assert_eq!(buf, [1, 2, 3, 4]);
+        assert_eq!(cursor.remaining(), 1);
+        assert_eq!(cursor.fill_to(&mut read, 3).unwrap(), FillStatus::Read(2));
+        assert_eq!(cursor.pos(), 0);
+        assert_eq!(cursor.filled(), &[5, 6, 7]);
+        assert_eq!(
+            cursor.read_exact_from(&mut read, &mut [0; 4]).unwrap(),
+            FillStatus::Eof
+        );
+        assert_eq!(cursor.remaining(), 3);
+        assert_eq!(
+            cursor.fill_to(&mut read, 7).unwrap(),
+            FillStatus::BufferFull
+        );
+        cursor.push_from_read(&mut &[8u8, 9, 10][..]).unwrap();
+        assert_eq!(
+            cursor.fill_from_read(&mut read).unwrap(),
+            FillStatus::BufferFull
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_full() {
+        use std::io::{ErrorKind, IoSlice, Write};
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        assert_eq!(cursor.write(&[1, 2, 3]).unwrap(), 3);
+        assert_eq!(cursor.write(&[4, 5]).unwrap(), 1);
+        assert_eq!(cursor.write(&[6]).unwrap(), 0);
+        assert_eq!(cursor.write_vectored(&[IoSlice::new(&[6])]).unwrap(), 0);
+        assert_eq!(
+            cursor.write_all(&[6]).unwrap_err().kind(),
+            ErrorKind::WriteZero
+        );
+        assert_eq!(cursor.filled(), &[1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn vectored() {
+        use std::io::{IoSlice, Write};
+        let mut first: Cursor<u8, 4> = Cursor::new();
+        let mut second: Cursor<u8, 4> = Cursor::new();
+        first.push(9).unwrap();
+        let status = Cursor::push_from_read_vectored(
+            &mut [&mut first, &mut second],
+            &mut &[1u8, 2, 3, 4, 5, 6][..],
+        )
+        .unwrap();
+        assert_eq!(status, FillStatus::Read(6));
+        assert_eq!(first.filled(), &[9, 1, 2, 3]);
+        assert_eq!(second.filled(), &[4, 5, 6]);
+
+        first.read().unwrap();
+        let mut out = [0u8; 5];
+        let write_len =
+            Cursor::push_to_write_vectored(&mut [&mut first, &mut second], &mut &mut out[..])
+                .unwrap();
+        assert_eq!(write_len, 5);
+        assert_eq!(out, [1, 2, 3, 4, 5]);
+        assert_eq!(first.filled_len(), 0);
+        assert_eq!(second.filled(), &[4, 5, 6]);
+        assert_eq!(second.remaining(), 1);
+
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        assert!(cursor.is_write_vectored());
+        let bufs = [IoSlice::new(&[1, 2]), IoSlice::new(&[3, 4, 5])];
+        assert_eq!(cursor.write_vectored(&bufs).unwrap(), 4);
+        assert_eq!(cursor.filled(), &[1, 2, 3, 4]);
+        assert_eq!(cursor.write_vectored(&bufs).unwrap(), 0);
+
+        let mut cursors: [Cursor<u8, 1>; 70] = core::array::from_fn(|_| Cursor::new());
+        let mut refs: std::vec::Vec<_> = cursors.iter_mut().collect();
+        let bytes: std::vec::Vec<u8> = (0..80).collect();
+        let status = Cursor::push_from_read_vectored(&mut refs, &mut &bytes[..]).unwrap();
+        assert_eq!(status, FillStatus::Read(70));
+        assert_eq!(refs[69].filled(), &[69]);
+        let status = Cursor::push_from_read_vectored(&mut refs, &mut &bytes[..]).unwrap();
+        assert_eq!(status, FillStatus::BufferFull);
+        let mut out = [0u8; 80];
+        let write_len = Cursor::push_to_write_vectored(&mut refs, &mut &mut out[..]).unwrap();
+        assert_eq!(write_len, 70);
+        assert_eq!(out[..70], bytes[..70]);
+        assert_eq!(
+            Cursor::push_from_read_vectored(&mut [&mut first], &mut &[][..]).unwrap(),
+            FillStatus::Eof
+        );
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_buf() {
+        use bytes::{Buf, BufMut};
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        cursor.put_u16(0x0102);
+        cursor.put_slice(&[3, 4, 5]);
+        assert_eq!(cursor.remaining_mut(), 3);
+        assert_eq!(cursor.filled(), &[1, 2, 3, 4, 5]);
+        assert_eq!(cursor.get_u16(), 0x0102);
+        assert_eq!(Buf::remaining(&cursor), 3);
+        assert_eq!(cursor.chunk(), &[3, 4, 5]);
+        cursor.advance(2);
+        assert_eq!(cursor.get_u8(), 5);
+        assert!(!cursor.has_remaining());
+        cursor.put_bytes(0, 3);
+        assert!(!cursor.has_remaining_mut());
+    }
+
+    #[test]
+    fn transmute_to_the_end() {
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        cursor.push_transmute(1u32).unwrap();
+        assert_eq!(cursor.filled_len(), 4);
+        cursor.set_transmute(0, 2u32).unwrap();
+        assert!(cursor.set_transmute(1, 2u32).is_err());
+        assert!(cursor.push_transmute(0u8).is_err());
+        assert_eq!(cursor.get_transmute_mut::<[u8; 2]>(2), Some(&mut [0, 0]));
+        assert!(cursor.get_transmute_mut::<[u8; 2]>(3).is_none());
+        assert!(cursor.get_transmute_mut::<[u8; 2]>(usize::MAX).is_none());
+        cursor.clear();
+        assert!(cursor.get_transmute_mut::<u8>(0).is_none());
+    }
+
+    #[test]
+    fn transmute_in_items() {
+        let mut cursor: Cursor<u16, 4> = Cursor::new();
+        cursor.push(1).unwrap();
+        cursor.push_transmute([2u16, 3]).unwrap();
+        assert_eq!(cursor.filled(), &[1, 2, 3]);
+        assert!(cursor.push_transmute(0u32).is_err());
+        cursor.set_transmute(2, [4u16, 5]).unwrap();
+        assert!(cursor.set_transmute(3, 0u32).is_err());
+        assert_eq!(cursor.filled(), &[1, 2, 4]);
+    }
+
+    #[test]
+    fn owned_fifo() {
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let mut cursor: Cursor<(usize, Rc<()>), 8> = Cursor::new();
+        for i in 0..6 {
+            cursor.push((i, tracker.clone())).unwrap();
+        }
+        assert_eq!(cursor.read().map(|item| item.0), Some(0));
+        assert_eq!(cursor.take().map(|item| item.0), Some(1));
+        assert_eq!(cursor.take().map(|item| item.0), Some(2));
+        assert_eq!(
+            (cursor.pos(), cursor.taken_len(), cursor.filled_len()),
+            (3, 3, 6)
+        );
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        assert_eq!(cursor.read().map(|item| item.0), Some(3));
+        cursor.compact();
+        assert_eq!(Rc::strong_count(&tracker), 3);
+        assert_eq!(cursor.taken_len(), 0);
+        assert_eq!(
+            cursor
+                .unread()
+                .iter()
+                .map(|item| item.0)
+                .collect::<Vec<_>>(),
+            [4, 5]
+        );
+
+        assert_eq!(cursor.take().map(|item| item.0), Some(4));
+        for i in 6..8 {
+            cursor.push((i, tracker.clone())).unwrap();
+        }
+        let mut drain = cursor.drain();
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next().map(|item| item.0), Some(5));
+        drop(drain);
+        assert_eq!(cursor.filled_len(), 1);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+        assert!(cursor.take().is_none());
+        cursor.compact();
+        assert_eq!(cursor.filled_len(), 0);
+
+        for i in 0..8 {
+            cursor.push((i, tracker.clone())).unwrap();
+        }
+        cursor.read();
+        cursor.take();
+        cursor.clear();
+        assert_eq!(Rc::strong_count(&tracker), 1);
+        for i in 0..8 {
+            cursor.push((i, tracker.clone())).unwrap();
+        }
+        cursor.take();
+        drop(cursor);
+        assert_eq!(Rc::strong_count(&tracker), 1);
     }
 }
@@ -0,0 +1,398 @@
+use core::marker::PhantomData;
+
+use crate::{
+    max, BigEndian, Cursor, DecodeError, EncodeError, LengthPrefix, LittleEndian, PrefixVarInt,
+    Primitive, String, Vec,
+};
+
+///Value that can be written into a [Cursor].
+///Derive it with `#[derive(Encode)]` behind the `derive` feature.
+pub trait Encode {
+    ///Upper bound of the encoded size, so packet buffers can be sized at compile time.
+    const MAX_ENCODED_SIZE: usize;
+
+    fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError>;
+}
+
+///Value that can be read from a [Cursor].
+///Derive it with `#[derive(Decode)]` behind the `derive` feature.
+pub trait Decode: Sized {
+    fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError>;
+}
+
+///Alternative encoding of `T`, selected on derived fields with `#[codec(...)]`.
+pub trait EncodeWith<T: ?Sized> {
+    const MAX_ENCODED_SIZE: usize;
+
+    fn encode_with<const N: usize>(
+        value: &T,
+        cursor: &mut Cursor<u8, N>,
+    ) -> Result<(), EncodeError>;
+}
+
+///Alternative decoding of `T`, selected on derived fields with `#[codec(...)]`.
+pub trait DecodeWith<T> {
+    fn decode_with<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<T, DecodeError>;
+}
+
+impl<const N: usize> Cursor<u8, N> {
+    ///Encodes `value`, leaving the cursor untouched on error.
+    pub fn encode<V: Encode + ?Sized>(&mut self, value: &V) -> Result<(), EncodeError> {
+        let filled_len = self.filled_len();
+        value.encode(self).inspect_err(|_| {
+            unsafe { *self.filled_len_mut() = filled_len };
+        })
+    }
+
+    ///Decodes a `V`, leaving `pos` untouched on error so incomplete packets can be retried.
+    pub fn decode<V: Decode>(&mut self) -> Result<V, DecodeError> {
+        let pos = self.pos();
+        V::decode(self).inspect_err(|_| {
+            unsafe { *self.pos_mut() = pos };
+        })
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {$(
+        impl Encode for $ty {
+            const MAX_ENCODED_SIZE: usize = <$ty as Primitive>::SIZE;
+
+            #[inline(always)]
+            fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+                <BigEndian as EncodeWith<$ty>>::encode_with(self, cursor)
+            }
+        }
+
+        impl Decode for $ty {
+            #[inline(always)]
+            fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+                <BigEndian as DecodeWith<$ty>>::decode_with(cursor)
+            }
+        }
+    )*};
+}
+
+impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! impl_byte_order {
+    ($($order:ty),*) => {$(
+        impl<P: Primitive> EncodeWith<P> for $order {
+            const MAX_ENCODED_SIZE: usize = P::SIZE;
+
+            #[inline(always)]
+            fn encode_with<const N: usize>(value: &P, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+                cursor
+                    .write_primitive::<$order, P>(*value)
+                    .map_err(|_| EncodeError::BufferFull)
+            }
+        }
+
+        impl<P: Primitive> DecodeWith<P> for $order {
+            #[inline(always)]
+            fn decode_with<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<P, DecodeError> {
+                cursor
+                    .read_primitive::<$order, P>()
+                    .ok_or(DecodeError::Incomplete)
+            }
+        }
+    )*};
+}
+
+impl_byte_order!(BigEndian, LittleEndian);
+
+impl Encode for bool {
+    const MAX_ENCODED_SIZE: usize = 1;
+
+    #[inline(always)]
+    fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+        (*self as u8).encode(cursor)
+    }
+}
+
+impl Decode for bool {
+    #[inline(always)]
+    fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+        match u8::decode(cursor)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError::Malformed),
+        }
+    }
+}
+
+///Encodes integers as VarInt or VarLong, selected with `#[codec(varint)]`.
+pub enum VarInt {}
+
+///Encodes signed integers as zigzag VarInt or VarLong, selected with `#[codec(zigzag)]`.
+pub enum ZigZag {}
+
+macro_rules! impl_varint {
+    ($($strategy:ty, $ty:ty, $max:expr => $write:ident, $read:ident;)*) => {$(
+        impl EncodeWith<$ty> for $strategy {
+            const MAX_ENCODED_SIZE: usize = $max;
+
+            #[inline(always)]
+            fn encode_with<const N: usize>(value: &$ty, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+                cursor.$write(*value as _).map_err(|_| EncodeError::BufferFull)
+            }
+        }
+
+        impl DecodeWith<$ty> for $strategy {
+            #[inline(always)]
+            fn decode_with<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<$ty, DecodeError> {
+                Ok(cursor.$read()? as $ty)
+            }
+        }
+    )*};
+}
+
+impl_varint! {
+    VarInt, i32, 5 => write_varint_i32, read_varint_i32;
+    VarInt, u32, 5 => write_leb128_u32, read_leb128_u32;
+    VarInt, i64, 10 => write_varlong_i64, read_varlong_i64;
+    VarInt, u64, 10 => write_leb128_u64, read_leb128_u64;
+    ZigZag, i32, 5 => write_zigzag_i32, read_zigzag_i32;
+    ZigZag, i64, 10 => write_zigzag_i64, read_zigzag_i64;
+}
+
+///Encodes strings and vecs after a `P` length prefix, selected with `#[codec(prefix = P)]`.
+pub struct Prefixed<P: LengthPrefix>(PhantomData<P>);
+
+impl<P: LengthPrefix, const M: usize> EncodeWith<String<M>> for Prefixed<P> {
+    const MAX_ENCODED_SIZE: usize = P::MAX_SIZE + M;
+
+    #[inline(always)]
+    fn encode_with<const N: usize>(
+        value: &String<M>,
+        cursor: &mut Cursor<u8, N>,
+    ) -> Result<(), EncodeError> {
+        cursor.write_str_prefixed::<P>(value.as_str())
+    }
+}
+
+impl<P: LengthPrefix, const M: usize> DecodeWith<String<M>> for Prefixed<P> {
+    #[inline(always)]
+    fn decode_with<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<String<M>, DecodeError> {
+        cursor.read_string_prefixed::<P, M>()
+    }
+}
+
+impl<P: LengthPrefix, T: Encode, const M: usize> EncodeWith<Vec<T, M>> for Prefixed<P> {
+    const MAX_ENCODED_SIZE: usize = P::MAX_SIZE + M * T::MAX_ENCODED_SIZE;
+
+    fn encode_with<const N: usize>(
+        value: &Vec<T, M>,
+        cursor: &mut Cursor<u8, N>,
+    ) -> Result<(), EncodeError> {
+        P::write(cursor, value.len())?;
+        for item in value {
+            item.encode(cursor)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: LengthPrefix, T: Decode, const M: usize> DecodeWith<Vec<T, M>> for Prefixed<P> {
+    fn decode_with<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Vec<T, M>, DecodeError> {
+        let len = P::read(cursor)?;
+        if len > M {
+            return Err(DecodeError::Capacity);
+        }
+        let mut vec = Vec::uninit();
+        for _ in 0..len {
+            unsafe { vec.push_unchecked(T::decode(cursor)?) };
+        }
+        Ok(vec)
+    }
+}
+
+impl<const M: usize> Encode for String<M> {
+    const MAX_ENCODED_SIZE: usize = <Prefixed<PrefixVarInt> as EncodeWith<Self>>::MAX_ENCODED_SIZE;
+
+    #[inline(always)]
+    fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+        Prefixed::<PrefixVarInt>::encode_with(self, cursor)
+    }
+}
+
+impl<const M: usize> Decode for String<M> {
+    #[inline(always)]
+    fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+        Prefixed::<PrefixVarInt>::decode_with(cursor)
+    }
+}
+
+impl<T: Encode, const M: usize> Encode for Vec<T, M> {
+    const MAX_ENCODED_SIZE: usize = <Prefixed<PrefixVarInt> as EncodeWith<Self>>::MAX_ENCODED_SIZE;
+
+    #[inline(always)]
+    fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+        Prefixed::<PrefixVarInt>::encode_with(self, cursor)
+    }
+}
+
+impl<T: Decode, const M: usize> Decode for Vec<T, M> {
+    #[inline(always)]
+    fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+        Prefixed::<PrefixVarInt>::decode_with(cursor)
+    }
+}
+
+impl<T: Encode, const L: usize> Encode for [T; L] {
+    const MAX_ENCODED_SIZE: usize = L * T::MAX_ENCODED_SIZE;
+
+    fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+        for item in self {
+            item.encode(cursor)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Decode, const L: usize> Decode for [T; L] {
+    fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+        let mut vec: Vec<T, L> = Vec::uninit();
+        for _ in 0..L {
+            unsafe { vec.push_unchecked(T::decode(cursor)?) };
+        }
+        Ok(unsafe { core::ptr::read(vec.as_array()) })
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    const MAX_ENCODED_SIZE: usize = 1 + T::MAX_ENCODED_SIZE;
+
+    fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+        self.is_some().encode(cursor)?;
+        match self {
+            Some(value) => value.encode(cursor),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+        if bool::decode(cursor)? {
+            Ok(Some(T::decode(cursor)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Encode for () {
+    const MAX_ENCODED_SIZE: usize = 0;
+
+    #[inline(always)]
+    fn encode<const N: usize>(&self, _cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl Decode for () {
+    #[inline(always)]
+    fn decode<const N: usize>(_cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($name:ident $index:tt),*))*) => {$(
+        impl<$($name: Encode),*> Encode for ($($name,)*) {
+            const MAX_ENCODED_SIZE: usize = 0 $(+ $name::MAX_ENCODED_SIZE)*;
+
+            fn encode<const N: usize>(&self, cursor: &mut Cursor<u8, N>) -> Result<(), EncodeError> {
+                $(self.$index.encode(cursor)?;)*
+                Ok(())
+            }
+        }
+
+        impl<$($name: Decode),*> Decode for ($($name,)*) {
+            fn decode<const N: usize>(cursor: &mut Cursor<u8, N>) -> Result<Self, DecodeError> {
+                Ok(($($name::decode(cursor)?,)*))
+            }
+        }
+    )*};
+}
+
+impl_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
+
+///Largest [Encode::MAX_ENCODED_SIZE] among enum variants, used by the derive macros.
+#[doc(hidden)]
+pub const fn max_encoded_size<const L: usize>(sizes: [usize; L]) -> usize {
+    let mut result = 0;
+    let mut i = 0;
+    while i < L {
+        result = max(result, sizes[i]);
+        i += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cursor, Decode, DecodeError, Encode, String, Vec};
+
+    #[test]
+    fn round_trip() {
+        let mut cursor: Cursor<u8, 128> = Cursor::new();
+        let mut vec = Vec::<u16, 4>::uninit();
+        for i in 1..4 {
+            vec.push(i).unwrap();
+        }
+        let value = (
+            7u8,
+            -3i64,
+            true,
+            Some(1.5f32),
+            String::<8>::from_array(*b"hi"),
+            [4u32; 2],
+        );
+        cursor.encode(&vec).unwrap();
+        cursor.encode(&value).unwrap();
+        cursor.encode(&None::<u64>).unwrap();
+        let decoded: Vec<u16, 4> = cursor.decode().unwrap();
+        assert_eq!(decoded.as_slice()[..decoded.len()], [1, 2, 3]);
+        let decoded: (u8, i64, bool, Option<f32>, String<8>, [u32; 2]) = cursor.decode().unwrap();
+        assert_eq!(decoded.0, 7);
+        assert_eq!(decoded.1, -3);
+        assert!(decoded.2);
+        assert_eq!(decoded.3, Some(1.5));
+        assert_eq!(decoded.4.as_str(), "hi");
+        assert_eq!(decoded.5, [4, 4]);
+        assert_eq!(cursor.decode::<Option<u64>>(), Ok(None));
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn max_encoded_size() {
+        assert_eq!(<(u8, u32, bool)>::MAX_ENCODED_SIZE, 6);
+        assert_eq!(Vec::<u16, 4>::MAX_ENCODED_SIZE, 5 + 8);
+        assert_eq!(String::<16>::MAX_ENCODED_SIZE, 5 + 16);
+        assert_eq!(Option::<[u8; 3]>::MAX_ENCODED_SIZE, 4);
+    }
+
+    #[test]
+    fn errors_leave_cursor_untouched() {
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        cursor.encode(&1u16).unwrap();
+        assert!(cursor.encode(&(1u16, 2u8)).is_err());
+        assert_eq!(cursor.filled_len(), 2);
+        assert_eq!(cursor.decode::<(u16, u8)>(), Err(DecodeError::Incomplete));
+        assert_eq!(cursor.pos(), 0);
+        cursor.encode(&2u8).unwrap();
+        assert_eq!(cursor.decode::<(u16, bool)>(), Err(DecodeError::Malformed));
+        assert_eq!(u16::decode(&mut cursor), Ok(1));
+    }
+}
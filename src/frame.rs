@@ -0,0 +1,127 @@
+use core::marker::PhantomData;
+
+use crate::{Cursor, DecodeError, EncodeError, LengthPrefix, PrefixVarInt};
+
+///Length-delimited framing over [Cursor] with the length prefix `P` and a maximum frame length.
+pub struct FrameCodec<P: LengthPrefix = PrefixVarInt> {
+    max_frame_len: usize,
+    _marker: PhantomData<P>,
+}
+
+impl<P: LengthPrefix> FrameCodec<P> {
+    ///`max_frame_len` is clamped to what `P` can represent.
+    pub const fn new(max_frame_len: usize) -> Self {
+        Self {
+            max_frame_len: crate::min(max_frame_len, P::MAX_LEN),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    ///Borrows the next complete frame straight from the buffer, advancing `pos` past it.
+    ///A partial frame is left unread and reported as [DecodeError::Incomplete],
+    ///a frame longer than the maximum or than the cursor can ever hold as [DecodeError::Capacity].
+    pub fn decode<'a, const N: usize>(
+        &self,
+        cursor: &'a mut Cursor<u8, N>,
+    ) -> Result<&'a [u8], DecodeError> {
+        let pos = cursor.pos();
+        let len = P::read(cursor);
+        let header = cursor.pos() - pos;
+        unsafe { *cursor.pos_mut() = pos };
+        match len {
+            Ok(len) if len > self.max_frame_len || header + len > N => Err(DecodeError::Capacity),
+            Ok(_) => cursor.read_bytes_prefixed::<P>(),
+            Err(err) => Err(err),
+        }
+    }
+
+    ///Reserves the prefix slot, lets `body` write the frame and backpatches its length.
+    ///The cursor is left untouched on error.
+    pub fn encode<const N: usize>(
+        &self,
+        cursor: &mut Cursor<u8, N>,
+        body: impl FnOnce(&mut Cursor<u8, N>) -> Result<(), EncodeError>,
+    ) -> Result<(), EncodeError> {
+        let mut slot = cursor.reserve_prefix::<P>(self.max_frame_len)?;
+        if let Err(err) = body(&mut slot) {
+            slot.cancel();
+            return Err(err);
+        }
+        slot.fill_with_len_since()
+    }
+
+    ///Writes `body` as one frame, leaving the cursor untouched on error.
+    pub fn encode_bytes<const N: usize>(
+        &self,
+        cursor: &mut Cursor<u8, N>,
+        body: &[u8],
+    ) -> Result<(), EncodeError> {
+        if body.len() > self.max_frame_len {
+            return Err(EncodeError::LengthOverflow);
+        }
+        cursor.write_bytes_prefixed::<P>(body)
+    }
+}
+
+impl<P: LengthPrefix> Clone for FrameCodec<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: LengthPrefix> Copy for FrameCodec<P> {}
+
+#[cfg(test)]
+mod test {
+    use super::FrameCodec;
+    use crate::{Cursor, DecodeError, EncodeError, LittleEndian, PrefixU16, PrefixVarInt};
+
+    #[test]
+    fn round_trip() {
+        let codec: FrameCodec<PrefixVarInt> = FrameCodec::new(300);
+        let mut cursor: Cursor<u8, 32> = Cursor::new();
+        codec
+            .encode(&mut cursor, |cursor| {
+                cursor.write_u16_be(0x0102).unwrap();
+                cursor.write_u8(3).map_err(|_| EncodeError::BufferFull)
+            })
+            .unwrap();
+        codec.encode_bytes(&mut cursor, b"hi").unwrap();
+        assert_eq!(cursor.filled(), &[3, 1, 2, 3, 2, b'h', b'i']);
+        assert_eq!(codec.decode(&mut cursor), Ok(&[1u8, 2, 3][..]));
+        assert_eq!(codec.decode(&mut cursor), Ok(&b"hi"[..]));
+        assert_eq!(codec.decode(&mut cursor), Err(DecodeError::Incomplete));
+    }
+
+    #[test]
+    fn partial_and_oversized() {
+        let codec: FrameCodec<PrefixU16<LittleEndian>> = FrameCodec::new(4);
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        cursor.push(3).unwrap();
+        assert_eq!(codec.decode(&mut cursor), Err(DecodeError::Incomplete));
+        cursor.push(0).unwrap();
+        cursor.push(7).unwrap();
+        assert_eq!(codec.decode(&mut cursor), Err(DecodeError::Incomplete));
+        assert_eq!(cursor.pos(), 0);
+        cursor.push(8).unwrap();
+        cursor.push(9).unwrap();
+        assert_eq!(codec.decode(&mut cursor), Ok(&[7u8, 8, 9][..]));
+
+        cursor.clear();
+        cursor.write_u16_le(5).unwrap();
+        assert_eq!(codec.decode(&mut cursor), Err(DecodeError::Capacity));
+        cursor.clear();
+        assert_eq!(
+            codec.encode(&mut cursor, |cursor| cursor
+                .write_bytes_prefixed::<PrefixVarInt>(b"long")
+                .map(|_| ())),
+            Err(EncodeError::LengthOverflow)
+        );
+        assert_eq!(cursor.filled_len(), 0);
+    }
+}
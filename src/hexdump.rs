@@ -0,0 +1,196 @@
+use core::{
+    fmt::{Debug, Display, Formatter, Result, Write},
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{Cursor, Vec};
+
+///Display adapter printing bytes as offset, hex and ASCII columns, 16 bytes per line.
+///A `>` precedes the byte at the read position and a `]` follows the last filled byte.
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    pos: Option<usize>,
+    filled_len: Option<usize>,
+}
+
+impl<'a> HexDump<'a> {
+    ///Dumps `bytes` without markers.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            offset: 0,
+            pos: None,
+            filled_len: None,
+        }
+    }
+
+    #[inline(always)]
+    fn marker(&self, index: usize) -> char {
+        if self.pos == Some(index) {
+            '>'
+        } else if self.filled_len == Some(index) {
+            ']'
+        } else {
+            ' '
+        }
+    }
+}
+
+impl Display for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (line_index, line) in self.bytes.chunks(16).enumerate() {
+            if line_index != 0 {
+                f.write_char('\n')?;
+            }
+            let start = self.offset + line_index * 16;
+            write!(f, "{:08x} ", start)?;
+            for i in 0..16 {
+                if i == 8 {
+                    f.write_char(' ')?;
+                }
+                match line.get(i) {
+                    Some(byte) => write!(f, "{}{:02x}", self.marker(start + i), byte)?,
+                    None if i == line.len() => write!(f, "{}  ", self.marker(start + i))?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            let end = if line.len() == 16 {
+                self.marker(start + 16)
+            } else {
+                ' '
+            };
+            write!(f, "{end} |")?;
+            for byte in line {
+                let ascii = if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                };
+                f.write_char(ascii)?;
+            }
+            f.write_char('|')?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Cursor<u8, N> {
+    ///Hexdump of `range` of the filled region, clamped to it.
+    pub fn hexdump(&self, range: impl RangeBounds<usize>) -> HexDump<'_> {
+        let filled_len = self.filled_len();
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.saturating_add(1),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => filled_len,
+        };
+        let end = crate::min(end, filled_len);
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let start = crate::min(start, end);
+        HexDump {
+            bytes: &self.filled()[start..end],
+            offset: start,
+            pos: Some(self.pos()),
+            filled_len: Some(filled_len),
+        }
+    }
+}
+
+///`{:?}` shows the positions, `{:#?}` adds a hexdump of the filled region.
+impl<const N: usize> Debug for Cursor<u8, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Cursor")
+            .field("pos", &self.pos())
+            .field("filled_len", &self.filled_len())
+            .field("capacity", &N)
+            .finish()?;
+        if f.alternate() && self.filled_len() != 0 {
+            write!(f, "\n{}", self.hexdump(..))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Vec<u8, N> {
+    ///Hexdump of the elements, also what [Display] prints.
+    #[inline(always)]
+    pub fn hexdump(&self) -> HexDump<'_> {
+        HexDump::new(&self.as_slice()[..self.len()])
+    }
+}
+
+impl<const N: usize> Display for Vec<u8, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.hexdump(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cursor, Vec};
+
+    #[test]
+    fn cursor_debug() {
+        let mut cursor: Cursor<u8, 32> = Cursor::new();
+        for byte in b"Hello, world!\n\x00\x01\x02\x03\x04" {
+            cursor.push(*byte).unwrap();
+        }
+        cursor.read_value::<[u8; 2]>().unwrap();
+        assert_eq!(
+            format!("{cursor:?}"),
+            "Cursor { pos: 2, filled_len: 19, capacity: 32 }"
+        );
+        assert_eq!(
+            format!("{cursor:#?}"),
+            "Cursor {
+    pos: 2,
+    filled_len: 19,
+    capacity: 32,
+}
+00000000  48 65>6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+00000010  02 03 04]                                         |...|"
+        );
+        assert_eq!(
+            cursor.hexdump(16..).to_string(),
+            "00000010  02 03 04]                                         |...|"
+        );
+        assert_eq!(
+            cursor.hexdump(4..=5).to_string(),
+            "00000004  6f 2c                                             |o,|"
+        );
+        assert_eq!(
+            cursor.hexdump(16..=usize::MAX).to_string(),
+            "00000010  02 03 04]                                         |...|"
+        );
+        assert_eq!(
+            cursor
+                .hexdump((
+                    core::ops::Bound::Excluded(usize::MAX),
+                    core::ops::Bound::Unbounded
+                ))
+                .to_string(),
+            ""
+        );
+    }
+
+    #[test]
+    fn vec_display() {
+        let vec: Vec<u8, 32> = Vec::from_array(*b"0123456789abcdef");
+        assert_eq!(
+            vec.to_string(),
+            "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|"
+        );
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        for byte in vec.iter() {
+            cursor.push(*byte).unwrap();
+        }
+        assert_eq!(
+            cursor.hexdump(..).to_string(),
+            "00000000 >30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66] |0123456789abcdef|"
+        );
+    }
+}
@@ -105,6 +105,55 @@ impl<'a, T, const N: usize> Iterator for SlabIterMut<'a, T, N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for IterableSlab<T, N> {
+    ///Serialized as a plain sequence of the elements in iteration order. Unlike [Slab],
+    ///removed indices are not kept, so deserializing assigns the elements fresh indices from 0.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self.iter() {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de>
+    for IterableSlab<T, N>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IterableSlabVisitor<T, const N: usize>(core::marker::PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::de::Visitor<'de>
+            for IterableSlabVisitor<T, N>
+        {
+            type Value = IterableSlab<T, N>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a sequence of at most {N} elements")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut slab = IterableSlab::new();
+                while let Some(value) = seq.next_element()? {
+                    if slab.len() == N {
+                        return Err(serde::de::Error::invalid_length(N + 1, &self));
+                    }
+                    slab.add_with_index(|_| value).unwrap();
+                }
+                Ok(slab)
+            }
+        }
+
+        deserializer.deserialize_seq(IterableSlabVisitor(core::marker::PhantomData))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::IterableSlab;
@@ -147,4 +196,26 @@ mod test {
         assert_eq!(iter.next().unwrap(), &3);
         assert!(iter.next().is_none());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde::{de::value::SeqDeserializer, Deserialize};
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+        let deserializer = SeqDeserializer::<_, serde::de::value::Error>::new([1u8, 2].into_iter());
+        let slab = IterableSlab::<u8, 2>::deserialize(deserializer).unwrap();
+        assert_ser_tokens(
+            &slab,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<IterableSlab<u8, 1>>(
+            &[Token::Seq { len: Some(2) }, Token::U8(1), Token::U8(2)],
+            "invalid length 2, expected a sequence of at most 1 elements",
+        );
+    }
 }
@@ -2,17 +2,54 @@
 #![feature(const_mut_refs)]
 #![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
+#![cfg_attr(feature = "std", feature(can_vector))]
 
+pub mod bit_cursor;
+pub mod byte_order;
+pub mod checksum;
+pub mod cipher;
+pub mod codec;
 pub mod cursor;
+pub mod encode;
+pub mod frame;
+pub mod hexdump;
+pub mod ring_cursor;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod slab;
+pub mod slot;
 pub mod string;
+pub mod transaction;
+pub mod varint;
 pub mod vec;
 pub mod iterable_slab;
+pub mod lz4;
+pub mod pod;
+pub mod quantize;
 
 pub use iterable_slab::*;
+pub use lz4::*;
+pub use bit_cursor::*;
+pub use byte_order::*;
+pub use checksum::*;
+pub use cipher::*;
+pub use codec::*;
 pub use cursor::*;
+pub use encode::*;
+pub use frame::*;
+pub use hexdump::*;
+pub use pod::*;
+
+#[cfg(feature = "derive")]
+pub use fast_collections_derive::{Decode, Encode, Pod};
+
+extern crate self as fast_collections;
+pub use ring_cursor::*;
 pub use slab::*;
+pub use slot::*;
 pub use string::*;
+pub use transaction::*;
+pub use varint::*;
 pub use vec::*;
 
 /// A const reimplementation of the [`transmute`](core::mem::transmute) function, avoiding copying
@@ -38,3 +75,11 @@ pub(crate) const fn min(value: usize, value2: usize) -> usize {
         value2
     }
 }
+
+pub(crate) const fn max(value: usize, value2: usize) -> usize {
+    if value > value2 {
+        value
+    } else {
+        value2
+    }
+}
@@ -0,0 +1,429 @@
+use crate::{Cursor, DecodeError, EncodeError, VarIntError};
+
+const MIN_MATCH: usize = 4;
+///The last match must start at least this many bytes before the end of the block.
+const MF_LIMIT: usize = 12;
+///The last bytes of a block are always literals.
+const LAST_LITERALS: usize = 5;
+const MAX_OFFSET: usize = u16::MAX as usize;
+
+///Largest size of an LZ4 block compressed from `len` bytes.
+pub const fn compress_bound(len: usize) -> usize {
+    len + len / 255 + 16
+}
+
+///Compresses `src` as one LZ4 block appended to `dst`, returning the compressed length.
+///`table` is the working hash table, its length must be a power of two.
+///`dst` is left untouched on error.
+pub fn compress_into<const M: usize, const H: usize>(
+    src: &[u8],
+    dst: &mut Cursor<u8, M>,
+    table: &mut [u32; H],
+) -> Result<usize, EncodeError> {
+    const { assert_table_len::<H>() };
+    let mut out = Output {
+        buffer: unsafe { dst.unfilled_mut() },
+        len: 0,
+    };
+    compress_block(src, &mut out, table)?;
+    let len = out.len;
+    unsafe { *dst.filled_len_mut() += len };
+    Ok(len)
+}
+
+///Same as [compress_into] with a 4096 entry hash table on the stack.
+pub fn compress_into_inline<const M: usize>(
+    src: &[u8],
+    dst: &mut Cursor<u8, M>,
+) -> Result<usize, EncodeError> {
+    compress_into(src, dst, &mut [0; 4096])
+}
+
+///Decompresses one LZ4 block appended to `dst`, returning the decompressed length.
+///`dst` is left untouched on error.
+pub fn decompress_into<const M: usize>(
+    src: &[u8],
+    dst: &mut Cursor<u8, M>,
+) -> Result<usize, DecodeError> {
+    let start = dst.filled_len();
+    let out = unsafe { dst.unfilled_mut() };
+    let len = decompress_block(src, out)?;
+    unsafe { *dst.filled_len_mut() = start + len };
+    Ok(len)
+}
+
+struct Output<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl Output<'_> {
+    #[inline(always)]
+    fn push(&mut self, byte: u8) -> Result<(), EncodeError> {
+        *self
+            .buffer
+            .get_mut(self.len)
+            .ok_or(EncodeError::BufferFull)? = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn extend(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.buffer
+            .get_mut(self.len..self.len + bytes.len())
+            .ok_or(EncodeError::BufferFull)?
+            .copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    ///Writes the remainder of a length that did not fit in its token nibble.
+    #[inline(always)]
+    fn length(&mut self, mut len: usize) -> Result<(), EncodeError> {
+        while len >= 255 {
+            self.push(255)?;
+            len -= 255;
+        }
+        self.push(len as u8)
+    }
+
+    fn sequence(
+        &mut self,
+        literals: &[u8],
+        offset: usize,
+        match_len: usize,
+    ) -> Result<(), EncodeError> {
+        let literal_len = literals.len();
+        let match_code = match_len.saturating_sub(MIN_MATCH);
+        let token = (crate::min(literal_len, 15) << 4) | crate::min(match_code, 15);
+        self.push(token as u8)?;
+        if literal_len >= 15 {
+            self.length(literal_len - 15)?;
+        }
+        self.extend(literals)?;
+        if match_len != 0 {
+            self.extend(&(offset as u16).to_le_bytes())?;
+            if match_code >= 15 {
+                self.length(match_code - 15)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const fn assert_table_len<const H: usize>() {
+    assert!(
+        H.is_power_of_two(),
+        "hash table length must be a power of two"
+    );
+}
+
+#[inline(always)]
+fn read_u32(src: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(src[index..index + 4].try_into().unwrap())
+}
+
+#[inline(always)]
+fn hash<const H: usize>(sequence: u32) -> usize {
+    (sequence.wrapping_mul(2654435761) >> (32 - H.trailing_zeros())) as usize
+}
+
+fn compress_block<const H: usize>(
+    src: &[u8],
+    out: &mut Output,
+    table: &mut [u32; H],
+) -> Result<(), EncodeError> {
+    let len = src.len();
+    let mut anchor = 0;
+    if len > MF_LIMIT {
+        table.fill(0);
+        let match_limit = len - LAST_LITERALS;
+        let mut i = 0;
+        while i + MF_LIMIT <= len {
+            let sequence = read_u32(src, i);
+            let slot = &mut table[hash::<H>(sequence)];
+            let candidate = *slot as usize;
+            *slot = i as u32;
+            if candidate >= i || i - candidate > MAX_OFFSET || read_u32(src, candidate) != sequence
+            {
+                i += 1;
+                continue;
+            }
+            let mut match_len = MIN_MATCH;
+            while i + match_len < match_limit && src[candidate + match_len] == src[i + match_len] {
+                match_len += 1;
+            }
+            out.sequence(&src[anchor..i], i - candidate, match_len)?;
+            i += match_len;
+            anchor = i;
+        }
+    }
+    out.sequence(&src[anchor..], 0, 0)
+}
+
+fn decompress_block(src: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+    let mut ip = 0;
+    let mut op = 0;
+    let byte = |ip: &mut usize| {
+        let byte = *src.get(*ip).ok_or(DecodeError::Malformed)?;
+        *ip += 1;
+        Ok::<_, DecodeError>(byte)
+    };
+    let length = |ip: &mut usize, mut len: usize| loop {
+        let extra = byte(ip)?;
+        len += extra as usize;
+        if extra != 255 {
+            return Ok::<_, DecodeError>(len);
+        }
+    };
+    loop {
+        let token = byte(&mut ip)?;
+        let mut literal_len = (token >> 4) as usize;
+        if literal_len == 15 {
+            literal_len = length(&mut ip, literal_len)?;
+        }
+        let literals = src
+            .get(ip..ip + literal_len)
+            .ok_or(DecodeError::Malformed)?;
+        out.get_mut(op..op + literal_len)
+            .ok_or(DecodeError::Capacity)?
+            .copy_from_slice(literals);
+        ip += literal_len;
+        op += literal_len;
+        if ip == src.len() {
+            return Ok(op);
+        }
+        let offset = u16::from_le_bytes([byte(&mut ip)?, byte(&mut ip)?]) as usize;
+        if offset == 0 || offset > op {
+            return Err(DecodeError::Malformed);
+        }
+        let mut match_len = (token & 15) as usize;
+        if match_len == 15 {
+            match_len = length(&mut ip, match_len)?;
+        }
+        match_len += MIN_MATCH;
+        if op + match_len > out.len() {
+            return Err(DecodeError::Capacity);
+        }
+        for i in op..op + match_len {
+            out[i] = out[i - offset];
+        }
+        op += match_len;
+    }
+}
+
+///Minecraft-style packet compression: bodies shorter than the threshold are sent raw after a
+///VarInt 0, longer ones as their VarInt length followed by an LZ4 block.
+pub struct Compression<const H: usize = 4096> {
+    threshold: usize,
+    table: [u32; H],
+}
+
+impl<const H: usize> Compression<H> {
+    pub const fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            table: [0; H],
+        }
+    }
+
+    #[inline(always)]
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    #[inline(always)]
+    pub fn set_threshold(&mut self, threshold: usize) {
+        self.threshold = threshold;
+    }
+
+    ///Appends `body` to `dst`, compressed when it reaches the threshold.
+    ///`dst` is left untouched on error.
+    pub fn encode<const M: usize>(
+        &mut self,
+        body: &[u8],
+        dst: &mut Cursor<u8, M>,
+    ) -> Result<(), EncodeError> {
+        let start = dst.filled_len();
+        let result = if body.len() < self.threshold {
+            dst.write_varint_i32(0)
+                .map_err(|_| EncodeError::BufferFull)
+                .and_then(|()| {
+                    let unfilled = unsafe { dst.unfilled_mut() };
+                    unfilled
+                        .get_mut(..body.len())
+                        .ok_or(EncodeError::BufferFull)?
+                        .copy_from_slice(body);
+                    unsafe { *dst.filled_len_mut() += body.len() };
+                    Ok(())
+                })
+        } else if body.len() > i32::MAX as usize {
+            Err(EncodeError::LengthOverflow)
+        } else {
+            dst.write_varint_i32(body.len() as i32)
+                .map_err(|_| EncodeError::BufferFull)
+                .and_then(|()| compress_into(body, dst, &mut self.table).map(|_| ()))
+        };
+        if result.is_err() {
+            unsafe { *dst.filled_len_mut() = start };
+        }
+        result
+    }
+
+    ///Appends the body of `packet` written by [Self::encode] to `dst`.
+    ///`dst` is left untouched on error.
+    pub fn decode<const M: usize>(
+        &self,
+        packet: &[u8],
+        dst: &mut Cursor<u8, M>,
+    ) -> Result<(), DecodeError> {
+        let (len, header) = read_varint(packet)?;
+        let data = &packet[header..];
+        if len == 0 {
+            let unfilled = unsafe { dst.unfilled_mut() };
+            unfilled
+                .get_mut(..data.len())
+                .ok_or(DecodeError::Capacity)?
+                .copy_from_slice(data);
+            unsafe { *dst.filled_len_mut() += data.len() };
+            return Ok(());
+        }
+        if len > M - dst.filled_len() {
+            return Err(DecodeError::Capacity);
+        }
+        let start = dst.filled_len();
+        if decompress_into(data, dst)? != len {
+            unsafe { *dst.filled_len_mut() = start };
+            return Err(DecodeError::Malformed);
+        }
+        Ok(())
+    }
+}
+
+///Reads a non-negative VarInt from the front of `bytes`, returning it with its length.
+fn read_varint(bytes: &[u8]) -> Result<(usize, usize), DecodeError> {
+    let mut value = 0u32;
+    for (i, byte) in bytes.iter().enumerate().take(5) {
+        value |= ((byte & 0x7F) as u32) << (i * 7);
+        if byte & 0x80 == 0 {
+            return match i32::try_from(value) {
+                Ok(value) => Ok((value as usize, i + 1)),
+                Err(_) => Err(DecodeError::Malformed),
+            };
+        }
+    }
+    Err(if bytes.len() >= 5 {
+        VarIntError::TooLong
+    } else {
+        VarIntError::Incomplete
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::{
+        compress_bound, compress_into, compress_into_inline, decompress_into, Compression,
+    };
+    use crate::{Cursor, DecodeError, EncodeError};
+
+    fn round_trip(src: &[u8]) -> usize {
+        let mut compressed: Cursor<u8, 20000> = Cursor::new();
+        let len = compress_into(src, &mut compressed, &mut [0; 1024]).unwrap();
+        assert!(len <= compress_bound(src.len()));
+        let mut decompressed: Cursor<u8, 20000> = Cursor::new();
+        assert_eq!(
+            decompress_into(compressed.filled(), &mut decompressed),
+            Ok(src.len())
+        );
+        assert_eq!(decompressed.filled(), src);
+        len
+    }
+
+    #[test]
+    fn block_round_trip() {
+        assert_eq!(round_trip(b""), 1);
+        round_trip(b"short");
+        round_trip(b"abcdabcdabcdabcd");
+        let text = b"the quick brown fox jumps over the lazy dog. ".repeat(100);
+        assert!(round_trip(&text) < text.len() / 10);
+        let zeros = [0u8; 10000];
+        assert!(round_trip(&zeros) < 100);
+        let mut rng = rand::thread_rng();
+        let noise: [u8; 4096] = core::array::from_fn(|_| rng.gen());
+        round_trip(&noise);
+        let mixed: [u8; 8192] = core::array::from_fn(|i| {
+            if i % 512 < 256 {
+                noise[i % 64]
+            } else {
+                rng.gen_range(0..4)
+            }
+        });
+        round_trip(&mixed);
+    }
+
+    #[test]
+    fn reference_block() {
+        //Produced by the reference lz4 implementation.
+        let block = [
+            0x1F, 0x61, 0x01, 0x00, 0x0F, 0x50, 0x62, 0x63, 0x64, 0x65, 0x66,
+        ];
+        let mut decompressed: Cursor<u8, 64> = Cursor::new();
+        decompress_into(&block, &mut decompressed).unwrap();
+        assert_eq!(
+            decompressed.filled(),
+            b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaabcdef"
+        );
+    }
+
+    #[test]
+    fn malformed_and_full() {
+        let mut dst: Cursor<u8, 8> = Cursor::new();
+        assert_eq!(
+            decompress_into(&[0x10, 0x61, 0x05, 0x00], &mut dst),
+            Err(DecodeError::Malformed)
+        );
+        assert_eq!(
+            decompress_into(&[0x20, 0x61], &mut dst),
+            Err(DecodeError::Malformed)
+        );
+        assert_eq!(
+            decompress_into(&[0x1F, 0x61, 0x01, 0x00, 0x00], &mut dst),
+            Err(DecodeError::Capacity)
+        );
+        assert_eq!(dst.filled_len(), 0);
+        assert_eq!(
+            compress_into_inline(&[1; 64], &mut dst),
+            Err(EncodeError::BufferFull)
+        );
+        assert_eq!(dst.filled_len(), 0);
+    }
+
+    #[test]
+    fn threshold() {
+        let mut compression: Compression = Compression::new(64);
+        let mut packet: Cursor<u8, 256> = Cursor::new();
+        compression.encode(b"tiny", &mut packet).unwrap();
+        assert_eq!(packet.filled(), b"\x00tiny");
+        let mut body: Cursor<u8, 256> = Cursor::new();
+        compression.decode(packet.filled(), &mut body).unwrap();
+        assert_eq!(body.filled(), b"tiny");
+
+        let large = [7u8; 200];
+        packet.clear();
+        body.clear();
+        compression.encode(&large, &mut packet).unwrap();
+        assert_eq!(&packet.filled()[..2], &[0xC8, 0x01]);
+        assert!(packet.filled_len() < 20);
+        compression.decode(packet.filled(), &mut body).unwrap();
+        assert_eq!(body.filled(), &large[..]);
+        let mut small: Cursor<u8, 100> = Cursor::new();
+        assert_eq!(
+            compression.decode(packet.filled(), &mut small),
+            Err(DecodeError::Capacity)
+        );
+    }
+}
@@ -0,0 +1,32 @@
+///Types for which every bit pattern is a valid value, so they can be read from any bytes.
+///# Safety
+///Implementors must not contain references, `bool`, `char`, enums or other types with invalid bit patterns.
+pub unsafe trait FromBytes: Copy {}
+
+///Types whose every byte is initialized, so they can be viewed as bytes.
+///# Safety
+///Implementors must not contain padding.
+pub unsafe trait AsBytes: Copy {}
+
+///Plain old data that is both [FromBytes] and [AsBytes].
+///Derive it with `#[derive(Pod)]` behind the `derive` feature.
+///# Safety
+///Implementors must uphold the contracts of [FromBytes] and [AsBytes].
+pub unsafe trait Pod: FromBytes + AsBytes + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {$(
+        unsafe impl FromBytes for $ty {}
+        unsafe impl AsBytes for $ty {}
+        unsafe impl Pod for $ty {}
+    )*};
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: FromBytes, const L: usize> FromBytes for [T; L] {}
+unsafe impl<T: AsBytes, const L: usize> AsBytes for [T; L] {}
+unsafe impl<T: Pod, const L: usize> Pod for [T; L] {}
+
+unsafe impl AsBytes for bool {}
+unsafe impl AsBytes for char {}
@@ -0,0 +1,311 @@
+use core::f64::consts::{FRAC_1_SQRT_2, TAU};
+
+use crate::BitCursor;
+
+impl<const N: usize> BitCursor<N> {
+    ///Writes `value` clamped to `min..=max` as one of `2^bits` evenly spaced steps.
+    ///[Self::read_quantized_f32] restores it within `(max - min) / (2^bits - 1) / 2`,
+    ///plus the rounding of the result to `f32`.
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn write_quantized_f32(
+        &mut self,
+        value: f32,
+        min: f32,
+        max: f32,
+        bits: u32,
+    ) -> Result<(), f32> {
+        self.write_bits(quantize(value as f64, min as f64, max as f64, bits), bits)
+            .map_err(|_| value)
+    }
+
+    ///Reads a value written by [Self::write_quantized_f32] with the same range and `bits`.
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn read_quantized_f32(&mut self, min: f32, max: f32, bits: u32) -> Option<f32> {
+        assert_bits(bits);
+        let steps = self.read_bits(bits)?;
+        Some(dequantize(steps, min as f64, max as f64, bits) as f32)
+    }
+
+    ///Writes an angle in radians as a fixed-point fraction of a full turn, wrapping it into `0..TAU`.
+    ///[Self::read_angle] restores it within `PI / 2^bits`, modulo a full turn.
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn write_angle(&mut self, radians: f32, bits: u32) -> Result<(), f32> {
+        assert_bits(bits);
+        let turns = radians as f64 / TAU;
+        let fraction = turns - turns.floor();
+        let steps = (fraction * (1u64 << bits) as f64).round() as u64 & max_steps(bits);
+        self.write_bits(steps, bits).map_err(|_| radians)
+    }
+
+    ///Reads an angle in `0..TAU` written by [Self::write_angle].
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn read_angle(&mut self, bits: u32) -> Option<f32> {
+        assert_bits(bits);
+        let steps = self.read_bits(bits)?;
+        Some((steps as f64 / (1u64 << bits) as f64 * TAU) as f32)
+    }
+
+    ///Writes a unit vector with octahedral encoding in `bits * 2` bits.
+    ///[Self::read_unit_vector] restores it within a distance of `5 / (2^bits - 1)`.
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn write_unit_vector(&mut self, vector: [f32; 3], bits: u32) -> Result<(), [f32; 3]> {
+        assert_bits(bits);
+        if self.unfilled_bits() < bits as usize * 2 {
+            return Err(vector);
+        }
+        let [x, y, z] = vector.map(|v| v as f64);
+        let l1 = x.abs() + y.abs() + z.abs();
+        let (mut u, mut v) = (x / l1, y / l1);
+        if z < 0.0 {
+            (u, v) = ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum());
+        }
+        unsafe {
+            self.write_bits(quantize(u, -1.0, 1.0, bits), bits)
+                .unwrap_unchecked();
+            self.write_bits(quantize(v, -1.0, 1.0, bits), bits)
+                .unwrap_unchecked();
+        }
+        Ok(())
+    }
+
+    ///Reads a unit vector written by [Self::write_unit_vector].
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn read_unit_vector(&mut self, bits: u32) -> Option<[f32; 3]> {
+        assert_bits(bits);
+        if self.remaining_bits() < bits as usize * 2 {
+            return None;
+        }
+        let u = dequantize(self.read_bits(bits)?, -1.0, 1.0, bits);
+        let v = dequantize(self.read_bits(bits)?, -1.0, 1.0, bits);
+        let z = 1.0 - u.abs() - v.abs();
+        let (x, y) = if z < 0.0 {
+            ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum())
+        } else {
+            (u, v)
+        };
+        let length = (x * x + y * y + z * z).sqrt();
+        Some([x, y, z].map(|v| (v / length) as f32))
+    }
+
+    ///Writes a unit quaternion with smallest-three encoding in `2 + bits * 3` bits,
+    ///dropping the largest component and storing the others in `-1/√2..=1/√2`.
+    ///[Self::read_quaternion] restores each stored component within `√2 / (2^bits - 1) / 2`
+    ///and the dropped one from the unit length, as `q` or the equivalent rotation `-q`.
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn write_quaternion(&mut self, quaternion: [f32; 4], bits: u32) -> Result<(), [f32; 4]> {
+        assert_bits(bits);
+        if self.unfilled_bits() < 2 + bits as usize * 3 {
+            return Err(quaternion);
+        }
+        let mut largest = 0;
+        for i in 1..4 {
+            if quaternion[i].abs() > quaternion[largest].abs() {
+                largest = i;
+            }
+        }
+        let sign = if quaternion[largest] < 0.0 { -1.0 } else { 1.0 };
+        unsafe {
+            self.write_bits(largest as u64, 2).unwrap_unchecked();
+            for (i, component) in quaternion.iter().enumerate() {
+                if i != largest {
+                    let steps = quantize(
+                        *component as f64 * sign,
+                        -FRAC_1_SQRT_2,
+                        FRAC_1_SQRT_2,
+                        bits,
+                    );
+                    self.write_bits(steps, bits).unwrap_unchecked();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Reads a unit quaternion written by [Self::write_quaternion].
+    ///
+    ///# Panics
+    ///Panics if `bits` is not in `1..=32`.
+    pub fn read_quaternion(&mut self, bits: u32) -> Option<[f32; 4]> {
+        assert_bits(bits);
+        if self.remaining_bits() < 2 + bits as usize * 3 {
+            return None;
+        }
+        let largest = self.read_bits(2)? as usize;
+        let mut quaternion = [0.0; 4];
+        let mut sum = 0.0;
+        for (i, component) in quaternion.iter_mut().enumerate() {
+            if i != largest {
+                let value = dequantize(self.read_bits(bits)?, -FRAC_1_SQRT_2, FRAC_1_SQRT_2, bits);
+                sum += value * value;
+                *component = value;
+            }
+        }
+        quaternion[largest] = f64::max(1.0 - sum, 0.0).sqrt();
+        Some(quaternion.map(|v| v as f32))
+    }
+}
+
+#[inline(always)]
+fn assert_bits(bits: u32) {
+    assert!(
+        (1..=32).contains(&bits),
+        "quantized values take 1 to 32 bits"
+    );
+}
+
+#[inline(always)]
+const fn max_steps(bits: u32) -> u64 {
+    (1 << bits) - 1
+}
+
+#[inline(always)]
+fn quantize(value: f64, min: f64, max: f64, bits: u32) -> u64 {
+    assert_bits(bits);
+    let normalized = ((value - min) / (max - min)).clamp(0.0, 1.0);
+    (normalized * max_steps(bits) as f64).round() as u64
+}
+
+#[inline(always)]
+fn dequantize(steps: u64, min: f64, max: f64, bits: u32) -> f64 {
+    min + steps as f64 / max_steps(bits) as f64 * (max - min)
+}
+
+#[cfg(test)]
+mod test {
+    use core::f32::consts::{PI, TAU};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::BitCursor;
+
+    #[test]
+    fn quantized_f32() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut cursor: BitCursor<4096> = BitCursor::new();
+        for bits in [1, 7, 16, 24] {
+            let bound = 2000.0 / ((1u64 << bits) - 1) as f32 / 2.0;
+            for _ in 0..64 {
+                let value = rng.gen_range(-1000.0..=1000.0);
+                cursor
+                    .write_quantized_f32(value, -1000.0, 1000.0, bits)
+                    .unwrap();
+                let restored = cursor.read_quantized_f32(-1000.0, 1000.0, bits).unwrap();
+                assert!(
+                    (restored - value).abs() <= bound + value.abs() * f32::EPSILON,
+                    "{value} {restored}"
+                );
+            }
+            cursor.clear();
+        }
+        cursor.write_quantized_f32(5.0, 0.0, 1.0, 8).unwrap();
+        assert_eq!(cursor.read_quantized_f32(0.0, 1.0, 8), Some(1.0));
+    }
+
+    #[test]
+    fn angle() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut cursor: BitCursor<1024> = BitCursor::new();
+        let bits = 12;
+        let bound = PI / (1 << bits) as f32;
+        for _ in 0..256 {
+            let angle = rng.gen_range(-10.0..10.0f32);
+            cursor.write_angle(angle, bits).unwrap();
+            let restored = cursor.read_angle(bits).unwrap();
+            let error = (restored - angle).rem_euclid(TAU);
+            assert!(
+                error.min(TAU - error) <= bound * 1.001,
+                "{angle} {restored}"
+            );
+        }
+    }
+
+    #[test]
+    fn unit_vector() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut cursor: BitCursor<4096> = BitCursor::new();
+        for bits in [8, 12, 16] {
+            let bound = 5.0 / ((1u64 << bits) - 1) as f32;
+            for _ in 0..256 {
+                let vector: [f32; 3] = core::array::from_fn(|_| rng.gen_range(-1.0..=1.0));
+                let length = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+                if length < 0.01 {
+                    continue;
+                }
+                let vector = vector.map(|v| v / length);
+                cursor.write_unit_vector(vector, bits).unwrap();
+                let restored = cursor.read_unit_vector(bits).unwrap();
+                let distance = (0..3)
+                    .map(|i| (restored[i] - vector[i]).powi(2))
+                    .sum::<f32>()
+                    .sqrt();
+                assert!(distance <= bound, "{vector:?} {restored:?}");
+            }
+            cursor.clear();
+        }
+        cursor.write_unit_vector([0.0, 0.0, -1.0], 8).unwrap();
+        assert_eq!(cursor.read_unit_vector(8), Some([0.0, 0.0, -1.0]));
+    }
+
+    #[test]
+    fn quaternion() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut cursor: BitCursor<4096> = BitCursor::new();
+        let bits = 10;
+        let bound = core::f32::consts::SQRT_2 / ((1u64 << bits) - 1) as f32 / 2.0;
+        for _ in 0..256 {
+            let quaternion: [f32; 4] = core::array::from_fn(|_| rng.gen_range(-1.0..=1.0));
+            let length = quaternion.iter().map(|v| v * v).sum::<f32>().sqrt();
+            if length < 0.01 {
+                continue;
+            }
+            let quaternion = quaternion.map(|v| v / length);
+            cursor.write_quaternion(quaternion, bits).unwrap();
+            let restored = cursor.read_quaternion(bits).unwrap();
+            let dot: f32 = (0..4).map(|i| quaternion[i] * restored[i]).sum();
+            let sign = dot.signum();
+            let largest = (0..4)
+                .max_by(|a, b| quaternion[*a].abs().total_cmp(&quaternion[*b].abs()))
+                .unwrap();
+            for i in (0..4).filter(|i| *i != largest) {
+                assert!(
+                    (restored[i] * sign - quaternion[i]).abs() <= bound * 1.001,
+                    "{quaternion:?} {restored:?}"
+                );
+            }
+            assert!(dot.abs() > 0.999, "{quaternion:?} {restored:?}");
+        }
+        let mut small: BitCursor<3> = BitCursor::new();
+        assert!(small.write_quaternion([1.0, 0.0, 0.0, 0.0], 10).is_err());
+        assert_eq!(small.bit_len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_too_many_bits() {
+        let mut cursor: BitCursor<16> = BitCursor::new();
+        cursor.write_bits(0, 32).unwrap();
+        cursor.write_bits(0, 32).unwrap();
+        cursor.read_angle(64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_zero_bits() {
+        let mut cursor: BitCursor<16> = BitCursor::new();
+        cursor.read_quantized_f32(0.0, 1.0, 0);
+    }
+}
@@ -0,0 +1,254 @@
+use core::{
+    mem::MaybeUninit,
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+///Fixed capacity queue whose reads and writes wrap around instead of requiring compaction.
+pub struct RingCursor<T, const N: usize> {
+    buffer: [MaybeUninit<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> RingCursor<T, N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            buffer: [const { MaybeUninit::uninit() }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    ///Number of readable items.
+    #[inline(always)]
+    pub const fn remaining(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[inline(always)]
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len < N {
+            unsafe { self.push_unchecked(item) };
+            Ok(())
+        } else {
+            Err(item)
+        }
+    }
+
+    ///# Safety
+    ///The ring must not be full.
+    #[inline(always)]
+    pub unsafe fn push_unchecked(&mut self, item: T) {
+        let tail = wrap::<N>(self.head + self.len);
+        *self.buffer.get_unchecked_mut(tail) = MaybeUninit::new(item);
+        self.len = self.len.unchecked_add(1);
+    }
+
+    #[inline(always)]
+    pub fn read(&mut self) -> Option<&T> {
+        if self.len != 0 {
+            Some(unsafe { self.read_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    ///# Safety
+    ///The ring must not be empty.
+    #[inline(always)]
+    pub unsafe fn read_unchecked(&mut self) -> &T {
+        let head = self.head;
+        self.head = wrap::<N>(head + 1);
+        self.len = self.len.unchecked_sub(1);
+        self.buffer.get_unchecked(head).assume_init_ref()
+    }
+
+    ///Readable region, split in two where it wraps around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head = self.head;
+        let ptr = self.buffer.as_ptr() as *const T;
+        let tail_len = N - head;
+        unsafe {
+            if self.len <= tail_len {
+                (from_raw_parts(ptr.add(head), self.len), &[])
+            } else {
+                (
+                    from_raw_parts(ptr.add(head), tail_len),
+                    from_raw_parts(ptr, self.len - tail_len),
+                )
+            }
+        }
+    }
+
+    ///Writable region, split in two where it wraps around the end of the buffer.
+    ///Items written here become readable after [Self::advance_filled].
+    ///# Safety
+    ///The returned items may be uninitialized and must be written before being read.
+    pub unsafe fn unfilled_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        let head = self.head;
+        let tail = head + self.len;
+        let ptr = self.buffer.as_mut_ptr() as *mut T;
+        if tail < N {
+            (
+                from_raw_parts_mut(ptr.add(tail), N - tail),
+                from_raw_parts_mut(ptr, head),
+            )
+        } else {
+            let tail = tail - N;
+            (from_raw_parts_mut(ptr.add(tail), head - tail), &mut [])
+        }
+    }
+
+    ///Marks `len` items of [Self::unfilled_slices_mut] as readable.
+    ///# Safety
+    ///The first `len` unfilled items must have been initialized.
+    #[inline(always)]
+    pub unsafe fn advance_filled(&mut self, len: usize) {
+        self.len = self.len.unchecked_add(len);
+    }
+
+    ///Discards up to `len` readable items, returning how many were discarded.
+    pub fn advance(&mut self, len: usize) -> usize {
+        let len = crate::min(len, self.len);
+        self.len -= len;
+        self.head = if self.len == 0 {
+            0
+        } else {
+            wrap::<N>(self.head + len)
+        };
+        len
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> RingCursor<u8, N> {
+    pub fn push_from_read<R: std::io::Read>(&mut self, read: &mut R) -> std::io::Result<usize> {
+        use std::io::IoSliceMut;
+        let (first, second) = unsafe { self.unfilled_slices_mut() };
+        let read_length =
+            read.read_vectored(&mut [IoSliceMut::new(first), IoSliceMut::new(second)])?;
+        if read_length == 0 {
+            use std::io::{Error, ErrorKind};
+            Err(Error::new(
+                ErrorKind::ConnectionAborted,
+                "read length was 0",
+            ))?;
+        }
+        unsafe { self.advance_filled(read_length) };
+        Ok(read_length)
+    }
+
+    ///Writes both readable halves once, discarding only what was written.
+    pub fn push_to_write<W: std::io::Write>(&mut self, write: &mut W) -> std::io::Result<usize> {
+        use std::io::IoSlice;
+        let (first, second) = self.as_slices();
+        let write_len = write.write_vectored(&[IoSlice::new(first), IoSlice::new(second)])?;
+        self.advance(write_len);
+        Ok(write_len)
+    }
+}
+
+#[inline(always)]
+const fn wrap<const N: usize>(index: usize) -> usize {
+    if index >= N {
+        index - N
+    } else {
+        index
+    }
+}
+
+impl<T: Copy, const N: usize> Clone for RingCursor<T, N> {
+    fn clone(&self) -> Self {
+        let mut cursor = Self {
+            buffer: [MaybeUninit::uninit(); N],
+            head: self.head,
+            len: self.len,
+        };
+        cursor.buffer.copy_from_slice(self.buffer.as_slice());
+        cursor
+    }
+}
+
+impl<T, const N: usize> Default for RingCursor<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RingCursor;
+
+    #[test]
+    fn wrap_around() {
+        let mut ring: RingCursor<u8, 4> = RingCursor::new();
+        for i in 1..5 {
+            ring.push(i).unwrap();
+        }
+        assert!(ring.push(5).is_err());
+        assert_eq!(ring.read(), Some(&1));
+        assert_eq!(ring.read(), Some(&2));
+        ring.push(5).unwrap();
+        ring.push(6).unwrap();
+        assert_eq!(ring.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        assert_eq!(ring.advance(3), 3);
+        assert_eq!(ring.as_slices(), (&[6][..], &[][..]));
+        assert_eq!(ring.advance(3), 1);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn unfilled_slices() {
+        let mut ring: RingCursor<u8, 4> = RingCursor::new();
+        for i in 1..4 {
+            ring.push(i).unwrap();
+        }
+        ring.advance(2);
+        let (first, second) = unsafe { ring.unfilled_slices_mut() };
+        assert_eq!((first.len(), second.len()), (1, 2));
+        first[0] = 4;
+        second[0] = 5;
+        unsafe { ring.advance_filled(2) };
+        assert_eq!(ring.as_slices(), (&[3, 4][..], &[5][..]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_write() {
+        let mut ring: RingCursor<u8, 4> = RingCursor::new();
+        ring.push_from_read(&mut &[1u8, 2, 3][..]).unwrap();
+        let mut written = std::vec::Vec::new();
+        assert_eq!(ring.push_to_write(&mut written).unwrap(), 3);
+        ring.push_from_read(&mut &[4u8, 5, 6][..]).unwrap();
+        ring.advance(1);
+        ring.push_from_read(&mut &[7u8, 8][..]).unwrap();
+        assert_eq!(ring.as_slices(), (&[5, 6, 7][..], &[8][..]));
+        let mut out = [0u8; 3];
+        assert_eq!(ring.push_to_write(&mut &mut out[..]).unwrap(), 3);
+        assert_eq!(&out[..3], &[5, 6, 7]);
+        assert_eq!(ring.as_slices(), (&[8][..], &[][..]));
+    }
+}
@@ -0,0 +1,791 @@
+//!Compact binary serde format writing into and reading from a [Cursor] without allocating.
+//!
+//!Integers are LEB128 VarInts, signed ones zigzag encoded, floats are little-endian and
+//!strings, byte arrays, sequences and maps are prefixed with their VarInt length.
+
+use core::fmt::{self, Display};
+
+use ::serde::{de, ser, Deserialize, Serialize};
+
+use crate::{Cursor, DecodeError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    ///The cursor has no room left for the serialized value.
+    BufferFull,
+    Decode(DecodeError),
+    ///Sequences and maps must know their length up front.
+    LengthRequired,
+    ///The format is not self-describing, so neither `deserialize_any` nor
+    ///`deserialize_ignored_any`, which would have to skip a value of unknown type, can be used.
+    AnyNotSupported,
+    ///Error raised by a [Serialize] or [Deserialize] implementation.
+    Custom(Message),
+}
+
+///Message of an [Error::Custom], truncated to 64 bytes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Message {
+    fn new<T: Display>(msg: T) -> Self {
+        let mut message = Self {
+            bytes: [0; 64],
+            len: 0,
+        };
+        let _ = fmt::Write::write_fmt(&mut message, format_args!("{msg}"));
+        message
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for Message {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut len = crate::min(s.len(), self.bytes.len() - self.len);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.bytes[self.len..self.len + len].copy_from_slice(&s.as_bytes()[..len]);
+        self.len += len;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BufferFull => f.write_str("cursor is full"),
+            Error::Decode(err) => write!(f, "decode failed: {err:?}"),
+            Error::LengthRequired => f.write_str("sequence length is required"),
+            Error::AnyNotSupported => f.write_str("the format is not self-describing"),
+            Error::Custom(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom(Message::new(msg))
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom(Message::new(msg))
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(value: DecodeError) -> Self {
+        Error::Decode(value)
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+///Serializes `value` into the unfilled region of `cursor`, leaving it untouched on error.
+pub fn to_cursor<T: Serialize + ?Sized, const N: usize>(
+    value: &T,
+    cursor: &mut Cursor<u8, N>,
+) -> Result<()> {
+    let filled_len = cursor.filled_len();
+    value
+        .serialize(&mut Serializer { cursor })
+        .inspect_err(|_| unsafe { *cursor.filled_len_mut() = filled_len })
+}
+
+///Deserializes a `T` from the unread region of `cursor`, advancing `pos` only on success.
+///Borrowed `&str` and `&[u8]` point straight into the cursor buffer.
+pub fn from_cursor<'de, T: Deserialize<'de>, const N: usize>(
+    cursor: &'de mut Cursor<u8, N>,
+) -> Result<T> {
+    let (unread, pos) = cursor.unread_and_pos_mut();
+    let mut deserializer = Deserializer { input: unread };
+    let value = T::deserialize(&mut deserializer)?;
+    *pos += unread.len() - deserializer.input.len();
+    Ok(value)
+}
+
+pub struct Serializer<'a, const N: usize> {
+    cursor: &'a mut Cursor<u8, N>,
+}
+
+impl<const N: usize> Serializer<'_, N> {
+    #[inline(always)]
+    fn write_varint(&mut self, value: u128) -> Result<()> {
+        self.cursor
+            .write_leb128_u128(value)
+            .map_err(|_| Error::BufferFull)
+    }
+
+    #[inline(always)]
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let unfilled = unsafe { self.cursor.unfilled_mut() };
+        if bytes.len() > unfilled.len() {
+            return Err(Error::BufferFull);
+        }
+        unfilled[..bytes.len()].copy_from_slice(bytes);
+        unsafe { *self.cursor.filled_len_mut() += bytes.len() };
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_len(&mut self, len: Option<usize>) -> Result<()> {
+        self.write_varint(len.ok_or(Error::LengthRequired)? as u128)
+    }
+}
+
+#[inline(always)]
+const fn zigzag(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+#[inline(always)]
+const fn unzigzag(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+
+impl<const N: usize> ser::Serializer for &mut Serializer<'_, N> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.write_bytes(&[v as u8])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.write_bytes(&[v as u8])
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.write_varint(zigzag(v as i128))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.write_varint(zigzag(v as i128))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.write_varint(zigzag(v as i128))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        self.write_varint(zigzag(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.write_bytes(&[v])
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        self.write_varint(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.write_varint(v.len() as u128)?;
+        self.write_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write_bytes(&[0])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        self.write_bytes(&[1])?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.write_varint(variant_index as u128)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_varint(variant_index as u128)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_varint(variant_index as u128)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_varint(variant_index as u128)?;
+        Ok(self)
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, _value: &T) -> Result<()> {
+        Err(Error::LengthRequired)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_serialize_compound {
+    ($($trait:ident, $method:ident;)*) => {$(
+        impl<const N: usize> ser::$trait for &mut Serializer<'_, N> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<()> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_serialize_compound! {
+    SerializeSeq, serialize_element;
+    SerializeTuple, serialize_element;
+    SerializeTupleStruct, serialize_field;
+    SerializeTupleVariant, serialize_field;
+}
+
+impl<const N: usize> ser::SerializeMap for &mut Serializer<'_, N> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_serialize_struct {
+    ($($trait:ident;)*) => {$(
+        impl<const N: usize> ser::$trait for &mut Serializer<'_, N> {
+            type Ok = ();
+            type Error = Error;
+
+            fn serialize_field<T: Serialize + ?Sized>(
+                &mut self,
+                _key: &'static str,
+                value: &T,
+            ) -> Result<()> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<()> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_serialize_struct! {
+    SerializeStruct;
+    SerializeStructVariant;
+}
+
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    #[inline(always)]
+    fn read_bytes(&mut self, len: usize) -> Result<&'de [u8]> {
+        if self.input.len() < len {
+            return Err(DecodeError::Incomplete.into());
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    #[inline(always)]
+    fn read_byte(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    ///Reads a VarInt of at most `bits` bits.
+    fn read_varint(&mut self, bits: u32) -> Result<u128> {
+        let mut value = 0u128;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_byte()?;
+            let low = (byte & 0x7F) as u128;
+            if shift >= bits || (bits - shift < 7 && low >> (bits - shift) != 0) {
+                return Err(DecodeError::Malformed.into());
+            }
+            value |= low << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    #[inline(always)]
+    fn read_len(&mut self) -> Result<usize> {
+        usize::try_from(self.read_varint(usize::BITS)?).map_err(|_| DecodeError::Malformed.into())
+    }
+
+    #[inline(always)]
+    fn read_signed(&mut self, bits: u32) -> Result<i128> {
+        Ok(unzigzag(self.read_varint(bits)?))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::AnyNotSupported)
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.read_byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            _ => Err(DecodeError::Malformed.into()),
+        }
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(self.read_byte()? as i8)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i16(self.read_signed(16)? as i16)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.read_signed(32)? as i32)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.read_signed(64)? as i64)
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i128(self.read_signed(128)?)
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.read_byte()?)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(self.read_varint(16)? as u16)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.read_varint(32)? as u32)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.read_varint(64)? as u64)
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u128(self.read_varint(128)?)
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.read_bytes(4)?;
+        visitor.visit_f32(f32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.read_bytes(8)?;
+        visitor.visit_f64(f64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.read_varint(32)? as u32;
+        visitor.visit_char(char::from_u32(value).ok_or(Error::Decode(DecodeError::Malformed))?)
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        let bytes = self.read_bytes(len)?;
+        visitor.visit_borrowed_str(
+            core::str::from_utf8(bytes).map_err(|_| Error::Decode(DecodeError::InvalidUtf8))?,
+        )
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_borrowed_bytes(self.read_bytes(len)?)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.read_byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(DecodeError::Malformed.into()),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_seq(Access {
+            deserializer: self,
+            len,
+        })
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Access {
+            deserializer: self,
+            len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_map(Access {
+            deserializer: self,
+            len,
+        })
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.read_varint(32)? as u32)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::AnyNotSupported)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct Access<'a, 'de> {
+    deserializer: &'a mut Deserializer<'de>,
+    len: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let value = seed.deserialize(&mut *self)?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_cursor, to_cursor, Error};
+    use crate::{Cursor, DecodeError, Slab, String, Vec};
+
+    #[test]
+    fn primitives() {
+        let mut cursor: Cursor<u8, 64> = Cursor::new();
+        to_cursor(
+            &(300u16, -1i32, true, 'a', 1.5f32, Some(7u64), ()),
+            &mut cursor,
+        )
+        .unwrap();
+        assert_eq!(&cursor.filled()[..4], &[0xac, 0x02, 0x01, 0x01]);
+        let value: (u16, i32, bool, char, f32, Option<u64>, ()) = from_cursor(&mut cursor).unwrap();
+        assert_eq!(value, (300, -1, true, 'a', 1.5, Some(7), ()));
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn borrowed() {
+        let mut cursor: Cursor<u8, 64> = Cursor::new();
+        to_cursor(&("steve", &b"\x01\x02"[..]), &mut cursor).unwrap();
+        {
+            let (name, bytes): (&str, &[u8]) = from_cursor(&mut cursor).unwrap();
+            assert_eq!(name, "steve");
+            assert_eq!(bytes, &[1, 2]);
+        }
+        assert_eq!(cursor.pos(), cursor.filled_len());
+    }
+
+    #[test]
+    fn collections() {
+        let mut cursor: Cursor<u8, 64> = Cursor::new();
+        let mut vec = Vec::<u16, 4>::uninit();
+        vec.push(1).unwrap();
+        vec.push(2).unwrap();
+        let mut slab = Slab::<u8, 4>::new();
+        slab.add_with_index(|_| 5).unwrap();
+        slab.add_with_index(|_| 6).unwrap();
+        unsafe { slab.remove_unchecked(0) };
+        to_cursor(&(vec, String::<8>::from_array(*b"hi"), slab), &mut cursor).unwrap();
+        let (vec, string, mut slab): (Vec<u16, 4>, String<8>, Slab<u8, 4>) =
+            from_cursor(&mut cursor).unwrap();
+        assert_eq!(vec.as_slice()[..vec.len()], [1, 2]);
+        assert_eq!(string.as_str(), "hi");
+        assert_eq!(slab.len(), 1);
+        assert_eq!(unsafe { slab.get_unchecked(1) }, &6);
+        assert_eq!(slab.add_with_index(|_| 7), Ok(0));
+    }
+
+    #[test]
+    fn errors_leave_cursor_untouched() {
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        to_cursor(&1u8, &mut cursor).unwrap();
+        assert_eq!(to_cursor(&"long", &mut cursor), Err(Error::BufferFull));
+        assert_eq!(cursor.filled_len(), 1);
+        assert_eq!(
+            from_cursor::<(u8, u8), 4>(&mut cursor),
+            Err(Error::Decode(DecodeError::Incomplete))
+        );
+        assert_eq!(cursor.pos(), 0);
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        let vec = Vec::<u8, 3>::from_array([1, 2, 3]);
+        to_cursor(&vec, &mut cursor).unwrap();
+        assert_eq!(
+            from_cursor::<Vec<u8, 2>, 8>(&mut cursor)
+                .map(|_| ())
+                .map_err(|err| std::string::ToString::to_string(&err)),
+            Err("invalid length 3, expected a sequence of at most 2 elements".into())
+        );
+        assert_eq!(cursor.pos(), 0);
+    }
+}
@@ -89,6 +89,71 @@ impl<T, const N: usize> Default for Slab<T, N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for Slab<T, N> {
+    ///Serialized as a sequence with `None` for removed indices, so indices survive a round trip.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let spares = unsafe {
+            core::slice::from_raw_parts(self.spares.as_ptr() as *const usize, self.spares_len)
+        };
+        let mut spare = [false; N];
+        for index in spares {
+            spare[*index] = true;
+        }
+        let mut seq = serializer.serialize_seq(Some(self.chunk_len))?;
+        for (index, spare) in spare.iter().enumerate().take(self.chunk_len) {
+            if *spare {
+                seq.serialize_element(&None::<T>)?;
+            } else {
+                seq.serialize_element(&Some(unsafe { self.get_unchecked(index) }))?;
+            }
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for Slab<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SlabVisitor<T, const N: usize>(core::marker::PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::de::Visitor<'de>
+            for SlabVisitor<T, N>
+        {
+            type Value = Slab<T, N>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a sequence of at most {N} optional elements")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut slab = Slab::new();
+                while let Some(value) = seq.next_element::<Option<T>>()? {
+                    let index = slab.chunk_len;
+                    if index == N {
+                        return Err(serde::de::Error::invalid_length(N + 1, &self));
+                    }
+                    match value {
+                        Some(value) => slab.chunk[index] = MaybeUninit::new(value),
+                        None => {
+                            slab.spares[slab.spares_len] = MaybeUninit::new(index);
+                            slab.spares_len += 1;
+                        }
+                    }
+                    slab.chunk_len = index + 1;
+                }
+                Ok(slab)
+            }
+        }
+
+        deserializer.deserialize_seq(SlabVisitor(core::marker::PhantomData))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Slab;
@@ -172,4 +237,31 @@ mod test {
             .unwrap();
         assert_eq!(id2, 1);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+        let mut slab: Slab<u8, 4> = Slab::new();
+        slab.add_with_index(|_| 1).unwrap();
+        slab.add_with_index(|_| 2).unwrap();
+        slab.add_with_index(|_| 3).unwrap();
+        unsafe { slab.remove_unchecked(1) };
+        assert_ser_tokens(
+            &slab,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::Some,
+                Token::U8(1),
+                Token::None,
+                Token::Some,
+                Token::U8(3),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<Slab<u8, 1>>(
+            &[Token::Seq { len: Some(2) }, Token::None, Token::None],
+            "invalid length 2, expected a sequence of at most 1 optional elements",
+        );
+    }
 }
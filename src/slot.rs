@@ -0,0 +1,297 @@
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{ByteOrder, Cursor, EncodeError, LengthPrefix, Mark, Prefixed, Primitive};
+
+///Placeholder reserved in a [Cursor] to be backpatched once the body after it is written.
+///The slot derefs to the cursor, so the body and nested slots are written through it.
+///
+///Clearing or compacting the cursor through the slot invalidates it, so filling it fails.
+///
+///Dropping a slot without filling or cancelling it fires a debug assertion.
+pub struct Slot<'a, V, const N: usize> {
+    cursor: &'a mut Cursor<u8, N>,
+    ///Taken right after the slot, so clearing or compacting the cursor is noticed
+    ///even once it was refilled past the slot.
+    end: Mark,
+    start: usize,
+    size: usize,
+    max_len: usize,
+    done: bool,
+    _marker: PhantomData<V>,
+}
+
+///Slot marker for a [Primitive] `P` written with byte order `B`.
+pub struct Ordered<B: ByteOrder, P: Primitive>(PhantomData<(B, P)>);
+
+impl<const N: usize> Cursor<u8, N> {
+    ///Reserves `P::SIZE` zeroed bytes to be set later with byte order `B`.
+    pub fn reserve<B: ByteOrder, P: Primitive>(
+        &mut self,
+    ) -> Result<Slot<'_, Ordered<B, P>, N>, EncodeError> {
+        Slot::new(self, P::SIZE, usize::MAX)
+    }
+
+    ///Reserves a length prefix wide enough for `max_len`, which is clamped to what `P` can represent.
+    ///Variable-width prefixes shrink on fill, shifting the body back.
+    pub fn reserve_prefix<P: LengthPrefix>(
+        &mut self,
+        max_len: usize,
+    ) -> Result<Slot<'_, Prefixed<P>, N>, EncodeError> {
+        let max_len = crate::min(max_len, P::MAX_LEN);
+        Slot::new(self, P::size(max_len), max_len)
+    }
+}
+
+impl<'a, V, const N: usize> Slot<'a, V, N> {
+    fn new(
+        cursor: &'a mut Cursor<u8, N>,
+        size: usize,
+        max_len: usize,
+    ) -> Result<Self, EncodeError> {
+        let start = cursor.filled_len();
+        if N - start < size {
+            return Err(EncodeError::BufferFull);
+        }
+        unsafe {
+            cursor.unfilled_mut()[..size].fill(0);
+            *cursor.filled_len_mut() = start + size;
+        }
+        Ok(Self {
+            end: cursor.mark_at(start + size),
+            cursor,
+            start,
+            size,
+            max_len,
+            done: false,
+            _marker: PhantomData,
+        })
+    }
+
+    ///Index of the slot in the cursor.
+    #[inline(always)]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    ///Number of bytes written after the slot, [None] once the slot is invalidated.
+    #[inline(always)]
+    pub fn len_since(&self) -> Option<usize> {
+        let end = self.start + self.size;
+        if self.cursor.mark_index(self.end) != Some(end) {
+            return None;
+        }
+        Some(self.cursor.filled_len() - end)
+    }
+
+    ///Discards the slot along with everything written after it.
+    pub fn cancel(mut self) {
+        if self.len_since().is_some() {
+            unsafe { *self.cursor.filled_len_mut() = self.start };
+        }
+        self.done = true;
+    }
+
+    #[inline(always)]
+    fn set_invalidated(mut self) -> Result<(), EncodeError> {
+        self.done = true;
+        Err(EncodeError::SlotInvalidated)
+    }
+}
+
+impl<B: ByteOrder, P: Primitive, const N: usize> Slot<'_, Ordered<B, P>, N> {
+    pub fn set(mut self, value: P) -> Result<(), EncodeError> {
+        self.done = true;
+        if self.len_since().is_none() {
+            return Err(EncodeError::SlotInvalidated);
+        }
+        unsafe { B::write(self.cursor.filled_mut().as_mut_ptr().add(self.start), value) };
+        Ok(())
+    }
+
+    ///Sets the slot to [Self::len_since], cancelling it when the length does not fit in `P`.
+    pub fn fill_with_len_since(self) -> Result<(), EncodeError>
+    where
+        P: TryFrom<usize>,
+    {
+        let Some(len) = self.len_since() else {
+            return self.set_invalidated();
+        };
+        match P::try_from(len) {
+            Ok(len) => self.set(len),
+            Err(_) => {
+                self.cancel();
+                Err(EncodeError::LengthOverflow)
+            }
+        }
+    }
+}
+
+impl<P: LengthPrefix, const N: usize> Slot<'_, Prefixed<P>, N> {
+    ///Writes [Self::len_since] as the prefix, shifting the body back when it takes fewer bytes
+    ///than reserved. The slot is cancelled when the length exceeds the reserved maximum.
+    pub fn fill_with_len_since(mut self) -> Result<(), EncodeError> {
+        let Some(len) = self.len_since() else {
+            return self.set_invalidated();
+        };
+        if len > self.max_len {
+            self.cancel();
+            return Err(EncodeError::LengthOverflow);
+        }
+        let (start, size) = (self.start, P::size(len));
+        let body = start + self.size;
+        let cursor = &mut *self.cursor;
+        unsafe {
+            cursor
+                .filled_mut()
+                .copy_within(body..body + len, start + size);
+            *cursor.filled_len_mut() = start;
+        }
+        P::write(cursor, len)?;
+        unsafe { *cursor.filled_len_mut() = start + size + len };
+        self.done = true;
+        Ok(())
+    }
+}
+
+impl<V, const N: usize> Deref for Slot<'_, V, N> {
+    type Target = Cursor<u8, N>;
+
+    fn deref(&self) -> &Self::Target {
+        self.cursor
+    }
+}
+
+impl<V, const N: usize> DerefMut for Slot<'_, V, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.cursor
+    }
+}
+
+impl<V, const N: usize> Drop for Slot<'_, V, N> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        debug_assert!(
+            self.done,
+            "slot was dropped without being filled or cancelled"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BigEndian, Cursor, EncodeError, LittleEndian, PrefixU8, PrefixVarInt};
+
+    #[test]
+    fn nested() {
+        let mut cursor: Cursor<u8, 512> = Cursor::new();
+        let mut outer = cursor.reserve_prefix::<PrefixVarInt>(400).unwrap();
+        outer.write_u8(1).unwrap();
+        let mut inner = outer.reserve::<BigEndian, u16>().unwrap();
+        inner.write_u16_be(0xABCD).unwrap();
+        let mut innermost = inner.reserve::<LittleEndian, u32>().unwrap();
+        innermost.write_u8(0xEF).unwrap();
+        innermost.fill_with_len_since().unwrap();
+        inner.fill_with_len_since().unwrap();
+        outer.fill_with_len_since().unwrap();
+        assert_eq!(
+            cursor.filled(),
+            &[10, 1, 0, 7, 0xAB, 0xCD, 1, 0, 0, 0, 0xEF]
+        );
+    }
+
+    #[test]
+    fn fill_and_overflow() {
+        let mut cursor: Cursor<u8, 400> = Cursor::new();
+        cursor.write_u8(9).unwrap();
+        let mut slot = cursor.reserve_prefix::<PrefixVarInt>(300).unwrap();
+        assert_eq!(slot.start(), 1);
+        for i in 0..200u8 {
+            slot.write_u8(i).unwrap();
+        }
+        assert_eq!(slot.len_since(), Some(200));
+        slot.fill_with_len_since().unwrap();
+        assert_eq!(&cursor.filled()[..4], &[9, 0xC8, 0x01, 0]);
+        assert_eq!(cursor.filled_len(), 203);
+
+        cursor.clear();
+        let mut slot = cursor.reserve_prefix::<PrefixU8>(2).unwrap();
+        slot.write_u16_be(1).unwrap();
+        slot.write_u8(2).unwrap();
+        assert_eq!(slot.fill_with_len_since(), Err(EncodeError::LengthOverflow));
+        assert_eq!(cursor.filled_len(), 0);
+
+        let mut slot = cursor.reserve::<BigEndian, u8>().unwrap();
+        slot.write_u16_be(1).unwrap();
+        slot.set(7).unwrap();
+        assert_eq!(cursor.filled(), &[7, 0, 1]);
+        let mut small: Cursor<u8, 3> = Cursor::new();
+        small.write_u8(0).unwrap();
+        small.reserve::<LittleEndian, u16>().unwrap().cancel();
+        assert_eq!(small.filled_len(), 1);
+        assert!(small.reserve::<BigEndian, u32>().is_err());
+    }
+
+    #[test]
+    fn invalidated() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        cursor.write_u16_be(1).unwrap();
+        let mut slot = cursor.reserve::<BigEndian, u16>().unwrap();
+        slot.clear();
+        slot.write_u8(1).unwrap();
+        assert_eq!(slot.len_since(), None);
+        assert_eq!(
+            slot.fill_with_len_since(),
+            Err(EncodeError::SlotInvalidated)
+        );
+
+        cursor.clear();
+        cursor.write_u16_be(1).unwrap();
+        cursor.read_u8().unwrap();
+        let mut slot = cursor.reserve_prefix::<PrefixU8>(8).unwrap();
+        slot.write_u16_be(2).unwrap();
+        slot.compact();
+        assert_eq!(
+            slot.fill_with_len_since(),
+            Err(EncodeError::SlotInvalidated)
+        );
+        assert_eq!(cursor.filled(), &[1, 0, 0, 2]);
+    }
+
+    #[test]
+    fn invalidated_and_refilled() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        let mut slot = cursor.reserve_prefix::<PrefixU8>(8).unwrap();
+        slot.write_u16_be(1).unwrap();
+        slot.clear();
+        slot.write_u32_be(2).unwrap();
+        assert_eq!(slot.len_since(), None);
+        assert_eq!(
+            slot.fill_with_len_since(),
+            Err(EncodeError::SlotInvalidated)
+        );
+        assert_eq!(cursor.filled(), &[0, 0, 0, 2]);
+
+        cursor.clear();
+        let mut slot = cursor.reserve::<BigEndian, u16>().unwrap();
+        slot.write_u16_be(0x0102).unwrap();
+        slot.read_u16_be().unwrap();
+        slot.compact();
+        slot.write_u32_be(0x03040506).unwrap();
+        assert_eq!(slot.set(0xFFFF), Err(EncodeError::SlotInvalidated));
+        assert_eq!(cursor.filled(), &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slot was dropped")]
+    #[cfg(debug_assertions)]
+    fn unfilled() {
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        let _slot = cursor.reserve::<BigEndian, u32>().unwrap();
+    }
+}
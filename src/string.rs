@@ -65,6 +65,41 @@ impl<const N: usize> Clone for String<N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for String<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for String<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StringVisitor<const N: usize>;
+
+        impl<const N: usize> serde::de::Visitor<'_> for StringVisitor<N> {
+            type Value = String<N>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a string of at most {N} bytes")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if value.len() > N {
+                    return Err(E::invalid_length(value.len(), &self));
+                }
+                let mut string = String::new();
+                let vec = string.as_vec_mut();
+                vec.as_array_mut()[..value.len()].copy_from_slice(value.as_bytes());
+                unsafe { *vec.len_mut() = value.len() };
+                Ok(string)
+            }
+        }
+
+        deserializer.deserialize_str(StringVisitor)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::String;
@@ -89,4 +124,21 @@ mod test {
         let string = String::<100>::from_array(*b"abcd");
         assert_eq!(string.as_str(), "abcd");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde::{de::IntoDeserializer, Deserialize};
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+        let deserializer = IntoDeserializer::<serde::de::value::Error>::into_deserializer("abcd");
+        assert_eq!(
+            String::<8>::deserialize(deserializer).unwrap().as_str(),
+            "abcd"
+        );
+        assert_ser_tokens(&String::<8>::from_array(*b"abcd"), &[Token::Str("abcd")]);
+        assert_de_tokens_error::<String<2>>(
+            &[Token::Str("abc")],
+            "invalid length 3, expected a string of at most 2 bytes",
+        );
+    }
 }
@@ -0,0 +1,201 @@
+use crate::{Cursor, DecodeError, VarIntError};
+
+///Saved read position of a [Cursor], see [Cursor::mark].
+///A mark taken before more than one [Cursor::drain] is rejected even when its item is still in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    ///Position counted from the first item ever pushed, so compaction does not shift it.
+    offset: usize,
+    ///Draining [Cursor::drain] calls made before the mark was taken.
+    drains: usize,
+}
+
+///The items a [Mark] points at were dropped by [Cursor::clear], [Cursor::compact] or [Cursor::take],
+///or moved out by [Cursor::drain].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidMark;
+
+///Error telling data that is not fully received yet apart from data that can never decode.
+pub trait MaybeIncomplete {
+    fn is_incomplete(&self) -> bool;
+}
+
+impl MaybeIncomplete for DecodeError {
+    #[inline(always)]
+    fn is_incomplete(&self) -> bool {
+        *self == DecodeError::Incomplete
+    }
+}
+
+impl MaybeIncomplete for VarIntError {
+    #[inline(always)]
+    fn is_incomplete(&self) -> bool {
+        *self == VarIntError::Incomplete
+    }
+}
+
+///Result of a [Cursor::transaction] closure, deciding whether its reads are rolled back.
+pub trait Outcome {
+    fn is_incomplete(&self) -> bool;
+}
+
+///[None] means the bytes ran out, as returned by the `read_*` methods.
+impl<T> Outcome for Option<T> {
+    #[inline(always)]
+    fn is_incomplete(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<T, E: MaybeIncomplete> Outcome for Result<T, E> {
+    #[inline(always)]
+    fn is_incomplete(&self) -> bool {
+        self.as_ref().is_err_and(E::is_incomplete)
+    }
+}
+
+impl<T, const N: usize> Cursor<T, N> {
+    ///Saves `pos` so the reads that follow can be undone with [Self::reset_to_mark].
+    #[inline(always)]
+    pub fn mark(&self) -> Mark {
+        self.mark_at(self.pos())
+    }
+
+    ///Mark at `index` instead of `pos`, such as the end of the filled items.
+    #[inline(always)]
+    pub fn mark_at(&self, index: usize) -> Mark {
+        Mark {
+            offset: self.discarded_len().wrapping_add(index),
+            drains: self.latest_drain().0,
+        }
+    }
+
+    ///Current index of the item `mark` was taken at, [None] once it was dropped.
+    #[inline(always)]
+    pub(crate) fn mark_index(&self, mark: Mark) -> Option<usize> {
+        let (drains, drained_at) = self.latest_drain();
+        let kept = mark.drains == drains
+            || mark.drains.wrapping_add(1) == drains
+                && drained_at.wrapping_sub(mark.offset) as isize >= 0;
+        let index = mark.offset.wrapping_sub(self.discarded_len());
+        (kept && (self.taken_len()..=self.filled_len()).contains(&index)).then_some(index)
+    }
+
+    ///Index `mark` is at now, clamped to where the items up to it are still in place:
+    ///the start of a later [Self::drain], the front after compaction and the filled items.
+    #[inline(always)]
+    pub(crate) fn mark_index_clamped(&self, mark: Mark) -> usize {
+        let (drains, drained_at) = self.latest_drain();
+        let mut offset = mark.offset;
+        if mark.drains != drains && offset.wrapping_sub(drained_at) as isize > 0 {
+            offset = drained_at;
+        }
+        let index = offset.wrapping_sub(self.discarded_len()) as isize;
+        crate::min(index.max(0) as usize, self.filled_len())
+    }
+
+    ///Moves `pos` back to `mark`, following the item it was taken at through compaction.
+    ///Fails when that item was dropped in the meantime, leaving `pos` untouched.
+    #[inline(always)]
+    pub fn reset_to_mark(&mut self, mark: Mark) -> Result<(), InvalidMark> {
+        let pos = self.mark_index(mark).ok_or(InvalidMark)?;
+        unsafe { *self.pos_mut() = pos };
+        Ok(())
+    }
+
+    ///Runs `f`, rolling `pos` back when it reports incomplete data so a partially received
+    ///packet can be decoded again once more bytes arrive. Reads are kept on success and on
+    ///errors that more bytes can not fix, so a malformed packet is skipped.
+    ///`pos` is left as is when `f` dropped the items it started at.
+    #[inline(always)]
+    pub fn transaction<R: Outcome>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let mark = self.mark();
+        let outcome = f(self);
+        if outcome.is_incomplete() {
+            let _ = self.reset_to_mark(mark);
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cursor, DecodeError, InvalidMark};
+
+    #[test]
+    fn transaction() {
+        let mut cursor: Cursor<u8, 16> = Cursor::new();
+        cursor.write_u16_be(7).unwrap();
+        cursor.write_u8(1).unwrap();
+        let partial = cursor.transaction(|tx| {
+            let id = tx.read_u16_be()?;
+            let len = tx.read_u8()?;
+            let body = tx.read_u16_be()?;
+            Some((id, len, body))
+        });
+        assert_eq!(partial, None);
+        assert_eq!(cursor.pos(), 0);
+        cursor.write_u16_be(300).unwrap();
+        let complete: Result<_, DecodeError> = cursor.transaction(|tx| {
+            let id = tx.read_u16_be().ok_or(DecodeError::Incomplete)?;
+            tx.read_u8().ok_or(DecodeError::Incomplete)?;
+            Ok((id, tx.read_u16_be().ok_or(DecodeError::Incomplete)?))
+        });
+        assert_eq!(complete, Ok((7, 300)));
+        assert_eq!(cursor.remaining(), 0);
+
+        cursor.write_u8(0xFF).unwrap();
+        let malformed: Result<u8, DecodeError> = cursor.transaction(|tx| match tx.read_u8() {
+            Some(0xFF) => Err(DecodeError::Malformed),
+            Some(byte) => Ok(byte),
+            None => Err(DecodeError::Incomplete),
+        });
+        assert_eq!(malformed, Err(DecodeError::Malformed));
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn mark() {
+        let mut cursor: Cursor<u8, 4> = Cursor::new();
+        cursor.push(1).unwrap();
+        cursor.push(2).unwrap();
+        let mark = cursor.mark();
+        assert_eq!(cursor.read(), Some(&1));
+        assert_eq!(cursor.read(), Some(&2));
+        cursor.reset_to_mark(mark).unwrap();
+        assert_eq!(cursor.read(), Some(&1));
+
+        let second = cursor.mark();
+        cursor.compact();
+        assert_eq!(cursor.reset_to_mark(mark), Err(InvalidMark));
+        assert_eq!(cursor.read(), Some(&2));
+        cursor.reset_to_mark(second).unwrap();
+        assert_eq!(cursor.read(), Some(&2));
+        cursor.clear();
+        assert_eq!(cursor.reset_to_mark(second), Err(InvalidMark));
+        assert_eq!(cursor.pos(), 0);
+    }
+
+    #[test]
+    fn mark_drain() {
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        for byte in 1..=4 {
+            cursor.push(byte).unwrap();
+        }
+        let start = cursor.mark();
+        cursor.read_value::<[u8; 2]>().unwrap();
+        let read = cursor.mark();
+        cursor.read().unwrap();
+        let past = cursor.mark();
+        cursor.reset_to_mark(read).unwrap();
+        assert_eq!(cursor.drain().count(), 2);
+        for byte in 5..=7 {
+            cursor.push(byte).unwrap();
+        }
+        assert_eq!(cursor.reset_to_mark(past), Err(InvalidMark));
+        cursor.reset_to_mark(start).unwrap();
+        cursor.reset_to_mark(read).unwrap();
+        assert_eq!(cursor.read(), Some(&5));
+        assert_eq!(cursor.reset_to_mark(past), Err(InvalidMark));
+    }
+}
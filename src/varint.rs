@@ -0,0 +1,192 @@
+use crate::Cursor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarIntError {
+    ///The buffer ends before the last byte, retry once more bytes are pushed.
+    Incomplete,
+    ///The encoding is longer than the integer type allows.
+    TooLong,
+}
+
+///Number of bytes `value` takes as an unsigned LEB128.
+pub const fn leb128_len(value: u128) -> usize {
+    if value == 0 {
+        1
+    } else {
+        (128 - value.leading_zeros() as usize).div_ceil(7)
+    }
+}
+
+///Number of bytes `value` takes as a VarInt.
+pub const fn varint_len(value: i32) -> usize {
+    leb128_len(value as u32 as u128)
+}
+
+///Number of bytes `value` takes as a VarLong.
+pub const fn varlong_len(value: i64) -> usize {
+    leb128_len(value as u64 as u128)
+}
+
+macro_rules! impl_leb128 {
+    ($($ty:ty => $write:ident, $read:ident;)*) => {
+        impl<const N: usize> Cursor<u8, N> {$(
+            ///Writes `value` as an unsigned LEB128, leaving the cursor untouched when it does not fit.
+            pub fn $write(&mut self, value: $ty) -> Result<(), $ty> {
+                let len = leb128_len(value as u128);
+                if self.filled_len() + len > N {
+                    return Err(value);
+                }
+                let unfilled = unsafe { self.unfilled_mut() };
+                let mut rest = value;
+                for byte in &mut unfilled[..len - 1] {
+                    *byte = rest as u8 | 0x80;
+                    rest >>= 7;
+                }
+                unfilled[len - 1] = rest as u8;
+                unsafe { *self.filled_len_mut() += len };
+                Ok(())
+            }
+
+            ///Reads an unsigned LEB128, advancing `pos` only when a whole value was read.
+            pub fn $read(&mut self) -> Result<$ty, VarIntError> {
+                const MAX_LEN: usize = (<$ty>::BITS as usize).div_ceil(7);
+                let pos = self.pos();
+                let unread = &self.filled()[pos..];
+                let mut value: $ty = 0;
+                for (i, byte) in unread.iter().take(MAX_LEN).enumerate() {
+                    value |= ((byte & 0x7F) as $ty) << (7 * i);
+                    if byte & 0x80 == 0 {
+                        unsafe { *self.pos_mut() = pos + i + 1 };
+                        return Ok(value);
+                    }
+                }
+                if unread.len() < MAX_LEN {
+                    Err(VarIntError::Incomplete)
+                } else {
+                    Err(VarIntError::TooLong)
+                }
+            }
+        )*}
+    };
+}
+
+impl_leb128! {
+    u32 => write_leb128_u32, read_leb128_u32;
+    u64 => write_leb128_u64, read_leb128_u64;
+    u128 => write_leb128_u128, read_leb128_u128;
+}
+
+impl<const N: usize> Cursor<u8, N> {
+    ///Writes a Minecraft-style VarInt, where negative values always take 5 bytes.
+    #[inline(always)]
+    pub fn write_varint_i32(&mut self, value: i32) -> Result<(), i32> {
+        self.write_leb128_u32(value as u32).map_err(|v| v as i32)
+    }
+
+    #[inline(always)]
+    pub fn read_varint_i32(&mut self) -> Result<i32, VarIntError> {
+        self.read_leb128_u32().map(|v| v as i32)
+    }
+
+    ///Writes a Minecraft-style VarLong, where negative values always take 10 bytes.
+    #[inline(always)]
+    pub fn write_varlong_i64(&mut self, value: i64) -> Result<(), i64> {
+        self.write_leb128_u64(value as u64).map_err(|v| v as i64)
+    }
+
+    #[inline(always)]
+    pub fn read_varlong_i64(&mut self) -> Result<i64, VarIntError> {
+        self.read_leb128_u64().map(|v| v as i64)
+    }
+
+    ///Writes a zigzag encoded VarInt, keeping small negative values short.
+    #[inline(always)]
+    pub fn write_zigzag_i32(&mut self, value: i32) -> Result<(), i32> {
+        self.write_leb128_u32(((value << 1) ^ (value >> 31)) as u32)
+            .map_err(|_| value)
+    }
+
+    #[inline(always)]
+    pub fn read_zigzag_i32(&mut self) -> Result<i32, VarIntError> {
+        self.read_leb128_u32()
+            .map(|v| ((v >> 1) as i32) ^ -((v & 1) as i32))
+    }
+
+    ///Writes a zigzag encoded VarLong, keeping small negative values short.
+    #[inline(always)]
+    pub fn write_zigzag_i64(&mut self, value: i64) -> Result<(), i64> {
+        self.write_leb128_u64(((value << 1) ^ (value >> 63)) as u64)
+            .map_err(|_| value)
+    }
+
+    #[inline(always)]
+    pub fn read_zigzag_i64(&mut self) -> Result<i64, VarIntError> {
+        self.read_leb128_u64()
+            .map(|v| ((v >> 1) as i64) ^ -((v & 1) as i64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{varint_len, varlong_len, VarIntError};
+    use crate::Cursor;
+
+    #[test]
+    fn varint_known_values() {
+        let cases: [(i32, &[u8]); 6] = [
+            (0, &[0x00]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x01]),
+            (25565, &[0xdd, 0xc7, 0x01]),
+            (i32::MAX, &[0xff, 0xff, 0xff, 0xff, 0x07]),
+            (-1, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+        ];
+        for (value, bytes) in cases {
+            let mut cursor: Cursor<u8, 8> = Cursor::new();
+            cursor.write_varint_i32(value).unwrap();
+            assert_eq!(cursor.filled(), bytes);
+            assert_eq!(varint_len(value), bytes.len());
+            assert_eq!(cursor.read_varint_i32(), Ok(value));
+        }
+    }
+
+    #[test]
+    fn varlong_and_zigzag() {
+        let mut cursor: Cursor<u8, 64> = Cursor::new();
+        cursor.write_varlong_i64(i64::MIN).unwrap();
+        assert_eq!(cursor.filled_len(), varlong_len(i64::MIN));
+        cursor.write_zigzag_i32(-1).unwrap();
+        cursor.write_zigzag_i64(-64).unwrap();
+        cursor.write_leb128_u128(u128::MAX).unwrap();
+        assert_eq!(cursor.filled_len(), 10 + 1 + 1 + 19);
+        assert_eq!(cursor.read_varlong_i64(), Ok(i64::MIN));
+        assert_eq!(cursor.read_zigzag_i32(), Ok(-1));
+        assert_eq!(cursor.read_zigzag_i64(), Ok(-64));
+        assert_eq!(cursor.read_leb128_u128(), Ok(u128::MAX));
+    }
+
+    #[test]
+    fn incomplete_and_too_long() {
+        let mut cursor: Cursor<u8, 8> = Cursor::new();
+        cursor.push(0x80).unwrap();
+        cursor.push(0x80).unwrap();
+        assert_eq!(cursor.read_varint_i32(), Err(VarIntError::Incomplete));
+        assert_eq!(cursor.pos(), 0);
+        cursor.push(0x01).unwrap();
+        assert_eq!(cursor.read_varint_i32(), Ok(1 << 14));
+        cursor.clear();
+        for _ in 0..5 {
+            cursor.push(0xff).unwrap();
+        }
+        assert_eq!(cursor.read_varint_i32(), Err(VarIntError::TooLong));
+        assert_eq!(cursor.pos(), 0);
+    }
+
+    #[test]
+    fn write_overflow() {
+        let mut cursor: Cursor<u8, 2> = Cursor::new();
+        assert_eq!(cursor.write_varint_i32(1 << 14), Err(1 << 14));
+        assert_eq!(cursor.filled_len(), 0);
+        cursor.write_varint_i32(128).unwrap();
+    }
+}
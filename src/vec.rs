@@ -1,7 +1,7 @@
 use core::{alloc, mem::MaybeUninit};
 use std::fmt::Debug;
 
-use crate::{const_transmute_unchecked, min};
+use crate::{const_transmute_unchecked, min, FromBytes, Pod};
 
 pub struct Vec<T, const N: usize> {
     data: [MaybeUninit<T>; N],
@@ -192,13 +192,13 @@ impl<T, const N: usize> Vec<T, N> {
     }
 
     #[inline(always)]
-    pub unsafe fn get_transmute_unchecked<V>(&self, index: usize) -> &V {
+    pub unsafe fn get_transmute_unchecked<V: FromBytes>(&self, index: usize) -> &V {
         let value = (&self.data) as *const _ as *const T;
         &*value.offset(index as isize).cast::<V>()
     }
 
     #[inline(always)]
-    pub unsafe fn get_transmute_mut_unchecked<V>(&mut self, index: usize) -> &mut V {
+    pub unsafe fn get_transmute_mut_unchecked<V: Pod>(&mut self, index: usize) -> &mut V {
         let value = (&mut self.data) as *mut _ as *mut T;
         &mut *value.offset(index as isize).cast::<V>()
     }
@@ -278,6 +278,48 @@ impl<T: Copy, const N: usize> Clone for Vec<T, N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for Vec<T, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for Vec<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VecVisitor<T, const N: usize>(core::marker::PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::de::Visitor<'de> for VecVisitor<T, N> {
+            type Value = Vec<T, N>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a sequence of at most {N} elements")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut vec = Vec::uninit();
+                while let Some(value) = seq.next_element()? {
+                    if vec.push(value).is_err() {
+                        return Err(serde::de::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Ok(vec)
+            }
+        }
+
+        deserializer.deserialize_seq(VecVisitor(core::marker::PhantomData))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::Vec;
@@ -353,4 +395,32 @@ mod test {
             let removed = vec.swap_remove(0).unwrap();
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde::{de::value::SeqDeserializer, Deserialize};
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+        let deserializer = SeqDeserializer::<_, serde::de::value::Error>::new([1u8, 2].into_iter());
+        let vec = Vec::<u8, 2>::deserialize(deserializer).unwrap();
+        assert_eq!(vec.as_slice()[..vec.len()], [1, 2]);
+        assert_ser_tokens(
+            &vec,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<Vec<u8, 2>>(
+            &[
+                Token::Seq { len: Some(3) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::U8(3),
+            ],
+            "invalid length 3, expected a sequence of at most 2 elements",
+        );
+    }
 }
@@ -0,0 +1,86 @@
+use fast_collections::{Cursor, DecodeError, Encode, PrefixU16, PrefixU8, String, Vec};
+use fast_collections_derive as macros;
+
+#[derive(macros::Encode, macros::Decode)]
+struct Handshake {
+    #[codec(varint)]
+    protocol: i32,
+    #[codec(prefix = PrefixU8)]
+    address: String<16>,
+    #[codec(le)]
+    port: u16,
+    next: State,
+}
+
+#[derive(macros::Encode, macros::Decode)]
+enum State {
+    Status,
+    Login(#[codec(zigzag)] i64),
+    Play { entities: Vec<u32, 4> },
+}
+
+#[derive(macros::Encode, macros::Decode)]
+struct Wrapper<T>(T, #[codec(prefix = PrefixU16)] Vec<u8, 8>);
+
+#[test]
+fn derived_round_trip() {
+    let mut cursor: Cursor<u8, 128> = Cursor::new();
+    let handshake = Handshake {
+        protocol: 767,
+        address: String::from_array(*b"localhost"),
+        port: 25565,
+        next: State::Login(-2),
+    };
+    cursor.encode(&handshake).unwrap();
+    assert_eq!(&cursor.filled()[..3], &[0xff, 0x05, 9]);
+    let decoded: Handshake = cursor.decode().unwrap();
+    assert_eq!(decoded.protocol, 767);
+    assert_eq!(decoded.address.as_str(), "localhost");
+    assert_eq!(decoded.port, 25565);
+    assert!(matches!(decoded.next, State::Login(-2)));
+
+    let mut entities = Vec::uninit();
+    entities.push(1).unwrap();
+    entities.push(2).unwrap();
+    cursor.encode(&State::Play { entities }).unwrap();
+    cursor.encode(&State::Status).unwrap();
+    match cursor.decode::<State>().unwrap() {
+        State::Play { entities } => assert_eq!(entities.len(), 2),
+        _ => panic!(),
+    }
+    assert!(matches!(cursor.decode::<State>(), Ok(State::Status)));
+
+    let mut bytes = Vec::uninit();
+    bytes.push(3).unwrap();
+    cursor.encode(&Wrapper(true, bytes)).unwrap();
+    let decoded: Wrapper<bool> = cursor.decode().unwrap();
+    assert!(decoded.0);
+    assert_eq!(decoded.1.len(), 1);
+}
+
+#[test]
+fn derived_max_encoded_size() {
+    assert_eq!(
+        Handshake::MAX_ENCODED_SIZE,
+        5 + (1 + 16) + 2 + State::MAX_ENCODED_SIZE
+    );
+    assert_eq!(State::MAX_ENCODED_SIZE, 1 + 5 + 4 * 4);
+    assert_eq!(Wrapper::<u32>::MAX_ENCODED_SIZE, 4 + 2 + 8);
+}
+
+#[test]
+fn unknown_variant() {
+    let mut cursor: Cursor<u8, 4> = Cursor::new();
+    cursor.push(3).unwrap();
+    assert_eq!(
+        cursor.decode::<State>().map(|_| ()),
+        Err(DecodeError::Malformed)
+    );
+    cursor.clear();
+    cursor.push(1).unwrap();
+    assert_eq!(
+        cursor.decode::<State>().map(|_| ()),
+        Err(DecodeError::Incomplete)
+    );
+    assert_eq!(cursor.pos(), 0);
+}
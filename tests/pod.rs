@@ -0,0 +1,48 @@
+use fast_collections::{Cursor, Pod};
+use fast_collections_derive as macros;
+
+#[derive(Debug, PartialEq, Clone, Copy, macros::Pod)]
+#[repr(C)]
+struct Position {
+    x: f32,
+    y: f32,
+    z: f32,
+    chunk: [u16; 2],
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, macros::Pod)]
+#[repr(transparent)]
+struct EntityId(u32);
+
+#[derive(Debug, PartialEq, Clone, Copy, macros::Pod)]
+#[repr(C, packed)]
+struct Packed {
+    tag: u8,
+    id: EntityId,
+}
+
+fn assert_pod<T: Pod>() {}
+
+#[test]
+fn derived_pod_round_trip() {
+    assert_pod::<Position>();
+    let mut cursor: Cursor<u8, 64> = Cursor::new();
+    let position = Position {
+        x: 1.0,
+        y: -2.0,
+        z: 3.5,
+        chunk: [4, 5],
+    };
+    cursor.push_transmute(position).unwrap();
+    cursor.push_transmute(EntityId(7)).unwrap();
+    cursor
+        .push_transmute(Packed {
+            tag: 1,
+            id: EntityId(9),
+        })
+        .unwrap();
+    assert_eq!(cursor.read_value::<Position>(), Some(position));
+    assert_eq!(cursor.read_value::<EntityId>(), Some(EntityId(7)));
+    let packed = cursor.read_value::<Packed>().unwrap();
+    assert_eq!({ packed.id }, EntityId(9));
+}